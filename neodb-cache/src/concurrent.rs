@@ -1,14 +1,58 @@
 //! Concurrent cache implementation
 
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use dashmap::DashMap;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use rand::Rng;
+use crate::lru::LruCache;
+
+/// Eviction strategy used once a `ConcurrentCache` reaches its capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// Exact least-recently-used eviction, tracked via a side ordering
+    /// structure guarded by a single mutex (same approach as
+    /// `CacheManager::l1_order`).
+    Lru,
+    /// Approximate LRU: on overflow, sample `sample_size` random entries
+    /// and evict whichever was accessed longest ago among the sample.
+    /// Avoids a global lock, keeping the `DashMap` fast path lock-free.
+    SampledApprox { sample_size: usize },
+}
+
+/// A cached value plus the bookkeeping needed for eviction and expiry.
+#[derive(Debug, Clone)]
+struct Slot<V> {
+    value: V,
+    last_accessed: Instant,
+    expires_at: Option<Instant>,
+}
+
+impl<V> Slot<V> {
+    fn new(value: V, ttl: Option<Duration>) -> Self {
+        let now = Instant::now();
+        Self {
+            value,
+            last_accessed: now,
+            expires_at: ttl.map(|d| now + d),
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        matches!(self.expires_at, Some(at) if Instant::now() >= at)
+    }
+}
 
 /// Thread-safe concurrent cache
 #[derive(Debug)]
 pub struct ConcurrentCache<K: Clone + Eq + std::hash::Hash, V> {
-    data: Arc<DashMap<K, V>>,
+    data: Arc<DashMap<K, Slot<V>>>,
     stats: Arc<RwLock<CacheStats>>,
+    capacity: Option<usize>,
+    policy: EvictionPolicy,
+    // Only populated under `EvictionPolicy::Lru`; tracks access order so
+    // eviction is O(1) instead of a full scan.
+    order: Option<Arc<Mutex<LruCache<K, ()>>>>,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -17,38 +61,108 @@ pub struct CacheStats {
     pub misses: u64,
     pub inserts: u64,
     pub removals: u64,
+    pub evictions: u64,
+    pub expirations: u64,
 }
 
 impl<K: Clone + Eq + std::hash::Hash, V: Clone> ConcurrentCache<K, V> {
+    /// Create an unbounded cache with no eviction policy.
     pub fn new() -> Self {
         Self {
             data: Arc::new(DashMap::new()),
             stats: Arc::new(RwLock::new(CacheStats::default())),
+            capacity: None,
+            policy: EvictionPolicy::Lru,
+            order: None,
+        }
+    }
+
+    /// Create a cache bounded to `capacity` entries, evicted under `policy`
+    /// once that capacity is exceeded.
+    pub fn with_capacity(capacity: usize, policy: EvictionPolicy) -> Self {
+        let order = match policy {
+            EvictionPolicy::Lru => Some(Arc::new(Mutex::new(LruCache::new(capacity)))),
+            EvictionPolicy::SampledApprox { .. } => None,
+        };
+        Self {
+            data: Arc::new(DashMap::new()),
+            stats: Arc::new(RwLock::new(CacheStats::default())),
+            capacity: Some(capacity),
+            policy,
+            order,
         }
     }
 
     pub fn get(&self, key: &K) -> Option<V> {
-        let result = self.data.get(key).map(|entry| entry.value().clone());
-        
+        // Resolve the hit/expired/miss outcome and drop the DashMap guard
+        // before touching `self.data` again, so an expired entry can be
+        // removed without deadlocking on its own shard lock.
+        let hit = self.data.get_mut(key).and_then(|mut slot| {
+            if slot.is_expired() {
+                None
+            } else {
+                slot.last_accessed = Instant::now();
+                Some(slot.value.clone())
+            }
+        });
+        let was_present = self.data.contains_key(key);
+
+        if hit.is_none() && was_present {
+            self.data.remove(key);
+            if let Some(order) = &self.order {
+                order.lock().remove(key);
+            }
+            let mut stats = self.stats.write();
+            stats.misses += 1;
+            stats.expirations += 1;
+            return None;
+        }
+
+        if hit.is_some() {
+            if let Some(order) = &self.order {
+                order.lock().get(key);
+            }
+        }
+
         let mut stats = self.stats.write();
-        if result.is_some() {
+        if hit.is_some() {
             stats.hits += 1;
         } else {
             stats.misses += 1;
         }
-        
-        result
+
+        hit
     }
 
+    /// Insert a value with no expiry.
     pub fn insert(&self, key: K, value: V) -> Option<V> {
-        let result = self.data.insert(key, value);
+        self.insert_with_ttl(key, value, None)
+    }
+
+    /// Insert a value that should be treated as a miss after `ttl` elapses.
+    pub fn insert_with_ttl(&self, key: K, value: V, ttl: Option<Duration>) -> Option<V> {
+        let slot = Slot::new(value, ttl);
+        let previous = self.data.insert(key.clone(), slot).map(|s| s.value);
         self.stats.write().inserts += 1;
-        result
+
+        if let Some(order) = &self.order {
+            if let Some((evicted_key, ())) = order.lock().put(key, ()) {
+                self.data.remove(&evicted_key);
+                self.stats.write().evictions += 1;
+            }
+        } else if let Some(capacity) = self.capacity {
+            self.evict_if_over_capacity(capacity);
+        }
+
+        previous
     }
 
     pub fn remove(&self, key: &K) -> Option<(K, V)> {
-        let result = self.data.remove(key);
+        let result = self.data.remove(key).map(|(k, slot)| (k, slot.value));
         if result.is_some() {
+            if let Some(order) = &self.order {
+                order.lock().remove(key);
+            }
             self.stats.write().removals += 1;
         }
         result
@@ -65,12 +179,76 @@ impl<K: Clone + Eq + std::hash::Hash, V: Clone> ConcurrentCache<K, V> {
     pub fn clear(&self) {
         let removed_count = self.data.len();
         self.data.clear();
+        if let Some(order) = &self.order {
+            order.lock().clear();
+        }
         self.stats.write().removals += removed_count as u64;
     }
 
     pub fn stats(&self) -> CacheStats {
         *self.stats.read()
     }
+
+    /// Sweep the cache and remove every entry whose TTL has elapsed,
+    /// returning the number of entries purged.
+    pub fn purge_expired(&self) -> usize {
+        let expired_keys: Vec<K> = self
+            .data
+            .iter()
+            .filter(|entry| entry.value().is_expired())
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        for key in &expired_keys {
+            self.data.remove(key);
+            if let Some(order) = &self.order {
+                order.lock().remove(key);
+            }
+        }
+
+        if !expired_keys.is_empty() {
+            self.stats.write().expirations += expired_keys.len() as u64;
+        }
+
+        expired_keys.len()
+    }
+
+    /// Sampled-approximate eviction used by `EvictionPolicy::SampledApprox`.
+    fn evict_if_over_capacity(&self, capacity: usize) {
+        let EvictionPolicy::SampledApprox { sample_size } = self.policy else {
+            return;
+        };
+
+        while self.data.len() > capacity {
+            let Some(victim) = self.sample_lru_candidate(sample_size) else {
+                break;
+            };
+            if self.data.remove(&victim).is_some() {
+                self.stats.write().evictions += 1;
+            }
+        }
+    }
+
+    /// Sample `sample_size` random entries and return the least-recently
+    /// accessed among them, without locking the whole map.
+    fn sample_lru_candidate(&self, sample_size: usize) -> Option<K> {
+        let len = self.data.len();
+        if len == 0 {
+            return None;
+        }
+
+        let skip = rand::thread_rng().gen_range(0..len);
+        // Wrap around to the start only for whatever the window starting at
+        // `skip` couldn't fill before reaching the end of the map.
+        let wrapped = sample_size.saturating_sub(len - skip);
+        self.data
+            .iter()
+            .skip(skip)
+            .take(sample_size)
+            .chain(self.data.iter().take(wrapped))
+            .min_by_key(|entry| entry.value().last_accessed)
+            .map(|entry| entry.key().clone())
+    }
 }
 
 impl<K: Clone + Eq + std::hash::Hash, V: Clone> Default for ConcurrentCache<K, V> {
@@ -84,6 +262,72 @@ impl<K: Clone + Eq + std::hash::Hash, V> Clone for ConcurrentCache<K, V> {
         Self {
             data: Arc::clone(&self.data),
             stats: Arc::clone(&self.stats),
+            capacity: self.capacity,
+            policy: self.policy,
+            order: self.order.clone(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_basic_insert_and_get() {
+        let cache = ConcurrentCache::new();
+        cache.insert("key1", "value1");
+        assert_eq!(cache.get(&"key1"), Some("value1"));
+        assert_eq!(cache.stats().hits, 1);
+    }
+
+    #[test]
+    fn test_lru_eviction_on_overflow() {
+        let cache = ConcurrentCache::with_capacity(2, EvictionPolicy::Lru);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.get(&"a"); // "b" is now the least-recently-used
+        cache.insert("c", 3);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn test_sampled_approx_eviction_respects_capacity() {
+        let cache = ConcurrentCache::with_capacity(5, EvictionPolicy::SampledApprox { sample_size: 3 });
+        for i in 0..20 {
+            cache.insert(i, i);
+        }
+        assert!(cache.len() <= 5);
+        assert!(cache.stats().evictions > 0);
+    }
+
+    #[test]
+    fn test_ttl_expiry_is_treated_as_miss() {
+        let cache = ConcurrentCache::new();
+        cache.insert_with_ttl("key1", "value1", Some(Duration::from_millis(10)));
+        assert_eq!(cache.get(&"key1"), Some("value1"));
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(cache.get(&"key1"), None);
+        assert_eq!(cache.stats().expirations, 1);
+    }
+
+    #[test]
+    fn test_purge_expired_removes_stale_entries() {
+        let cache = ConcurrentCache::new();
+        cache.insert_with_ttl("a", 1, Some(Duration::from_millis(10)));
+        cache.insert("b", 2);
+
+        std::thread::sleep(Duration::from_millis(20));
+        let purged = cache.purge_expired();
+
+        assert_eq!(purged, 1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&"b"), Some(2));
+    }
+}
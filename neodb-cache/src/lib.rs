@@ -8,7 +8,7 @@ pub mod concurrent;
 pub mod manager;
 
 pub use lru::LruCache;
-pub use concurrent::ConcurrentCache;
+pub use concurrent::{ConcurrentCache, EvictionPolicy};
 pub use manager::CacheManager;
 
 /// Cache result type
@@ -3,47 +3,264 @@
 use std::collections::HashMap;
 use std::hash::Hash;
 
-/// Simple LRU cache implementation
+/// Hit/miss counters for an [`LruCache`], mirroring [`crate::concurrent::CacheStats`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+const NIL: usize = usize::MAX;
+
+/// A slot in the intrusive doubly-linked list backing [`LruCache`].
+#[derive(Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    prev: usize,
+    next: usize,
+}
+
+/// A correct, O(1) least-recently-used cache.
+///
+/// Recency is tracked with an intrusive doubly-linked list threaded through a
+/// `Vec<Entry<K, V>>`: `get`/`put` move the touched slot to the head, and
+/// inserting past `capacity` evicts the tail. Freed slots are recycled via a
+/// free list so the backing `Vec` never grows past `capacity`.
 #[derive(Debug)]
 pub struct LruCache<K, V> {
     capacity: usize,
-    map: HashMap<K, V>,
-    // TODO: Add proper LRU ordering with linked list
+    slots: Vec<Option<Entry<K, V>>>,
+    index: HashMap<K, usize>,
+    free: Vec<usize>,
+    head: usize,
+    tail: usize,
+    stats: CacheStats,
 }
 
 impl<K: Clone + Hash + Eq, V> LruCache<K, V> {
     pub fn new(capacity: usize) -> Self {
         Self {
             capacity,
-            map: HashMap::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+            free: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            stats: CacheStats::default(),
         }
     }
 
+    /// Get a value, marking it as most recently used.
     pub fn get(&mut self, key: &K) -> Option<&V> {
-        // TODO: Update LRU order
-        self.map.get(key)
+        if let Some(&slot) = self.index.get(key) {
+            self.move_to_head(slot);
+            self.stats.hits += 1;
+            Some(&self.entry(slot).value)
+        } else {
+            self.stats.misses += 1;
+            None
+        }
+    }
+
+    /// Get a value without affecting recency order.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.index.get(key).map(|&slot| &self.entry(slot).value)
     }
 
-    pub fn put(&mut self, key: K, value: V) -> Option<V> {
-        if self.map.len() >= self.capacity && !self.map.contains_key(&key) {
-            // TODO: Evict LRU item
+    /// Insert a value, evicting the least-recently-used entry if the cache is
+    /// at capacity. Returns the evicted `(K, V)` pair, if any.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(&slot) = self.index.get(&key) {
+            self.entry_mut(slot).value = value;
+            self.move_to_head(slot);
+            return None;
+        }
+
+        let mut evicted = None;
+        if self.capacity == 0 {
+            return Some((key, value));
+        }
+        if self.index.len() >= self.capacity {
+            evicted = self.evict_tail();
         }
-        self.map.insert(key, value)
+
+        let slot = self.alloc_slot(key.clone(), value);
+        self.index.insert(key, slot);
+        self.push_front(slot);
+
+        evicted
     }
 
     pub fn remove(&mut self, key: &K) -> Option<V> {
-        self.map.remove(key)
+        let slot = self.index.remove(key)?;
+        self.unlink(slot);
+        let entry = self.release_slot(slot);
+        Some(entry.value)
     }
 
     pub fn len(&self) -> usize {
-        self.map.len()
+        self.index.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.map.is_empty()
+        self.index.is_empty()
     }
 
     pub fn clear(&mut self) {
-        self.map.clear();
+        self.slots.clear();
+        self.index.clear();
+        self.free.clear();
+        self.head = NIL;
+        self.tail = NIL;
+    }
+
+    /// Hit/miss/eviction counters accumulated since creation.
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    fn entry(&self, slot: usize) -> &Entry<K, V> {
+        self.slots[slot].as_ref().expect("slot index must be live")
+    }
+
+    fn entry_mut(&mut self, slot: usize) -> &mut Entry<K, V> {
+        self.slots[slot].as_mut().expect("slot index must be live")
+    }
+
+    fn alloc_slot(&mut self, key: K, value: V) -> usize {
+        let entry = Some(Entry {
+            key,
+            value,
+            prev: NIL,
+            next: NIL,
+        });
+
+        if let Some(slot) = self.free.pop() {
+            self.slots[slot] = entry;
+            slot
+        } else {
+            self.slots.push(entry);
+            self.slots.len() - 1
+        }
+    }
+
+    fn release_slot(&mut self, slot: usize) -> Entry<K, V> {
+        self.free.push(slot);
+        self.slots[slot].take().expect("slot index must be live")
+    }
+
+    fn evict_tail(&mut self) -> Option<(K, V)> {
+        if self.tail == NIL {
+            return None;
+        }
+
+        let slot = self.tail;
+        let key = self.entry(slot).key.clone();
+        self.unlink(slot);
+        self.index.remove(&key);
+        let entry = self.release_slot(slot);
+        self.stats.evictions += 1;
+        Some((entry.key, entry.value))
+    }
+
+    fn push_front(&mut self, slot: usize) {
+        self.entry_mut(slot).prev = NIL;
+        self.entry_mut(slot).next = self.head;
+
+        if self.head != NIL {
+            self.entry_mut(self.head).prev = slot;
+        }
+        self.head = slot;
+
+        if self.tail == NIL {
+            self.tail = slot;
+        }
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let (prev, next) = {
+            let entry = self.entry(slot);
+            (entry.prev, entry.next)
+        };
+
+        if prev != NIL {
+            self.entry_mut(prev).next = next;
+        } else {
+            self.head = next;
+        }
+
+        if next != NIL {
+            self.entry_mut(next).prev = prev;
+        } else {
+            self.tail = prev;
+        }
+    }
+
+    fn move_to_head(&mut self, slot: usize) {
+        if self.head == slot {
+            return;
+        }
+        self.unlink(slot);
+        self.push_front(slot);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        assert!(cache.put("a", 1).is_none());
+        assert!(cache.put("b", 2).is_none());
+
+        // Touch "a" so "b" becomes the LRU entry.
+        assert_eq!(cache.get(&"a"), Some(&1));
+
+        let evicted = cache.put("c", 3);
+        assert_eq!(evicted, Some(("b", 2)));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.peek(&"a").is_some());
+        assert!(cache.peek(&"c").is_some());
+        assert!(cache.peek(&"b").is_none());
+    }
+
+    #[test]
+    fn test_peek_does_not_affect_recency() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+
+        assert_eq!(cache.peek(&"a"), Some(&1));
+        // "a" is still the LRU entry since peek didn't touch recency.
+        let evicted = cache.put("c", 3);
+        assert_eq!(evicted, Some(("a", 1)));
+    }
+
+    #[test]
+    fn test_stats_track_hits_and_misses() {
+        let mut cache = LruCache::new(1);
+        cache.put("a", 1);
+
+        assert!(cache.get(&"a").is_some());
+        assert!(cache.get(&"missing").is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+
+        assert_eq!(cache.remove(&"a"), Some(1));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.peek(&"a").is_none());
+    }
+}
@@ -3,9 +3,13 @@
 //! Provides a unified interface for managing multiple cache types
 //! and implementing cache hierarchies.
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use dashmap::DashMap;
+use parking_lot::Mutex;
 use serde::{Serialize, Deserialize};
+use crate::lru::LruCache;
 use crate::{Result, CacheError};
 
 /// Configuration for the cache manager
@@ -37,7 +41,40 @@ pub struct CacheManager {
     config: CacheConfig,
     l1_cache: Arc<DashMap<String, CacheEntry>>,
     l2_cache: Arc<DashMap<String, CacheEntry>>,
-    stats: CacheStats,
+    // Tracks L1 recency so eviction is O(1) instead of scanning every entry
+    // for the oldest `last_accessed` timestamp.
+    l1_order: Mutex<LruCache<String, ()>>,
+    // Same, for L2: lets `demote_to_l2` evict L2's own least-recently-used
+    // entry to make room instead of refusing whatever L1 demotes into it.
+    l2_order: Mutex<LruCache<String, ()>>,
+    stats: AtomicCacheStats,
+}
+
+/// Lock-free counters backing `CacheStats`, updated directly from `get`/`put`
+/// without going through the `DashMap`-guarded cache layers.
+#[derive(Debug, Default)]
+struct AtomicCacheStats {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
+    compressed_bytes: AtomicU64,
+    uncompressed_bytes: AtomicU64,
+}
+
+impl AtomicCacheStats {
+    fn snapshot(&self, l1_size: usize, l2_size: usize) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
+            l1_size,
+            l2_size,
+            compressed_bytes: self.compressed_bytes.load(Ordering::Relaxed),
+            uncompressed_bytes: self.uncompressed_bytes.load(Ordering::Relaxed),
+        }
+    }
 }
 
 /// Cache entry with metadata
@@ -78,11 +115,15 @@ impl CacheManager {
 
     /// Create a new cache manager with custom configuration
     pub fn with_config(config: CacheConfig) -> Self {
+        let l1_size = config.l1_size;
+        let l2_size = config.l2_size;
         Self {
             config,
             l1_cache: Arc::new(DashMap::new()),
             l2_cache: Arc::new(DashMap::new()),
-            stats: CacheStats::default(),
+            l1_order: Mutex::new(LruCache::new(l1_size)),
+            l2_order: Mutex::new(LruCache::new(l2_size)),
+            stats: AtomicCacheStats::default(),
         }
     }
 
@@ -92,11 +133,17 @@ impl CacheManager {
         if let Some(mut entry) = self.l1_cache.get_mut(key) {
             if !self.is_entry_expired(&entry) {
                 entry.access();
-                return Ok(Some(entry.data.clone()));
+                let stored = entry.data.clone();
+                drop(entry);
+                self.l1_order.lock().get(&key.to_string());
+                self.stats.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Some(self.decompress(&stored)?));
             } else {
                 // Remove expired entry
                 drop(entry);
                 self.l1_cache.remove(key);
+                self.l1_order.lock().remove(&key.to_string());
+                self.stats.expirations.fetch_add(1, Ordering::Relaxed);
             }
         }
 
@@ -104,39 +151,97 @@ impl CacheManager {
         if let Some(mut entry) = self.l2_cache.get_mut(key) {
             if !self.is_entry_expired(&entry) {
                 entry.access();
-                let data = entry.data.clone();
-                
+                let stored = entry.data.clone();
+                drop(entry);
+
                 // Promote to L1 cache
-                self.promote_to_l1(key, data.clone());
-                
-                return Ok(Some(data));
+                self.promote_to_l1(key, stored.clone());
+
+                self.stats.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Some(self.decompress(&stored)?));
             } else {
                 // Remove expired entry
                 drop(entry);
                 self.l2_cache.remove(key);
+                self.l2_order.lock().remove(&key.to_string());
+                self.stats.expirations.fetch_add(1, Ordering::Relaxed);
             }
         }
 
+        self.stats.misses.fetch_add(1, Ordering::Relaxed);
         Ok(None)
     }
 
     /// Put a value in cache
     pub async fn put(&self, key: &str, value: Vec<u8>) -> Result<()> {
-        let entry = CacheEntry::new(value);
-
-        // Always put in L1 first
-        if self.l1_cache.len() >= self.config.l1_size {
-            self.evict_l1().await;
-        }
+        let stored = self.compress(value)?;
+        let entry = CacheEntry::new(stored);
 
         self.l1_cache.insert(key.to_string(), entry);
+        if let Some((evicted_key, ())) = self.l1_order.lock().put(key.to_string(), ()) {
+            self.demote_to_l2(&evicted_key);
+        }
         Ok(())
     }
 
+    /// Sweep both cache layers and remove every entry whose TTL has elapsed,
+    /// rather than waiting for it to be found lazily on a future access.
+    /// Returns the number of entries purged. A no-op if no TTL is configured.
+    pub async fn purge_expired(&self) -> usize {
+        let Some(ttl) = self.config.ttl_seconds else {
+            return 0;
+        };
+
+        let expired_l1: Vec<String> = self
+            .l1_cache
+            .iter()
+            .filter(|entry| entry.value().is_expired(ttl))
+            .map(|entry| entry.key().clone())
+            .collect();
+        for key in &expired_l1 {
+            self.l1_cache.remove(key);
+            self.l1_order.lock().remove(key);
+        }
+
+        let expired_l2: Vec<String> = self
+            .l2_cache
+            .iter()
+            .filter(|entry| entry.value().is_expired(ttl))
+            .map(|entry| entry.key().clone())
+            .collect();
+        for key in &expired_l2 {
+            self.l2_cache.remove(key);
+            self.l2_order.lock().remove(key);
+        }
+
+        let purged = expired_l1.len() + expired_l2.len();
+        self.stats.expirations.fetch_add(purged as u64, Ordering::Relaxed);
+        purged
+    }
+
+    /// Spawn a background task that periodically calls `purge_expired` on
+    /// `sweep_interval`, so TTL'd entries are reclaimed even if nothing ever
+    /// accesses them again. A no-op loop if no TTL is configured.
+    pub fn spawn_ttl_sweeper(self: &Arc<Self>, sweep_interval: Duration) -> tokio::task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            if manager.config.ttl_seconds.is_none() {
+                return;
+            }
+            let mut ticker = tokio::time::interval(sweep_interval);
+            loop {
+                ticker.tick().await;
+                manager.purge_expired().await;
+            }
+        })
+    }
+
     /// Remove a value from cache
     pub async fn remove(&self, key: &str) -> Result<bool> {
         let l1_removed = self.l1_cache.remove(key).is_some();
+        self.l1_order.lock().remove(&key.to_string());
         let l2_removed = self.l2_cache.remove(key).is_some();
+        self.l2_order.lock().remove(&key.to_string());
         Ok(l1_removed || l2_removed)
     }
 
@@ -144,12 +249,14 @@ impl CacheManager {
     pub async fn clear(&self) -> Result<()> {
         self.l1_cache.clear();
         self.l2_cache.clear();
+        self.l1_order.lock().clear();
+        self.l2_order.lock().clear();
         Ok(())
     }
 
-    /// Get cache statistics
-    pub fn stats(&self) -> &CacheStats {
-        &self.stats
+    /// Get a live snapshot of cache statistics
+    pub fn stats(&self) -> CacheStats {
+        self.stats.snapshot(self.l1_cache.len(), self.l2_cache.len())
     }
 
     /// Check if cache contains a key
@@ -167,47 +274,59 @@ impl CacheManager {
         }
     }
 
-    fn promote_to_l1(&self, key: &str, data: Vec<u8>) {
-        if self.l1_cache.len() >= self.config.l1_size {
-            // Would need to implement proper eviction
-            return;
-        }
-
-        let entry = CacheEntry::new(data);
+    /// Promote an entry from L2 to L1. `stored` is the already-compressed
+    /// (if enabled) byte representation, so it's re-wrapped as-is rather
+    /// than compressed a second time.
+    fn promote_to_l1(&self, key: &str, stored: Vec<u8>) {
+        let entry = CacheEntry::new(stored);
         self.l1_cache.insert(key.to_string(), entry);
         self.l2_cache.remove(key);
+        self.l2_order.lock().remove(&key.to_string());
+
+        if let Some((evicted_key, ())) = self.l1_order.lock().put(key.to_string(), ()) {
+            self.demote_to_l2(&evicted_key);
+        }
     }
 
-    async fn evict_l1(&self) {
-        // Simple eviction: move least recently used to L2
-        if let Some((key, entry)) = self.find_lru_in_l1() {
-            // Move to L2 if there's space
-            if self.l2_cache.len() < self.config.l2_size {
-                self.l2_cache.insert(key.clone(), entry);
+    /// Evict `key` from L1 into L2, evicting L2's own least-recently-used
+    /// entry to make room if L2 is already full (mirroring how `put`
+    /// demotes L1's own LRU entry here in the first place) rather than
+    /// refusing the incoming entry.
+    fn demote_to_l2(&self, key: &str) {
+        if let Some((_, entry)) = self.l1_cache.remove(key) {
+            self.l2_cache.insert(key.to_string(), entry);
+            if let Some((evicted_key, ())) = self.l2_order.lock().put(key.to_string(), ()) {
+                self.l2_cache.remove(&evicted_key);
+                self.stats.evictions.fetch_add(1, Ordering::Relaxed);
             }
-            self.l1_cache.remove(&key);
         }
     }
 
-    fn find_lru_in_l1(&self) -> Option<(String, CacheEntry)> {
-        let mut oldest_key = None;
-        let mut oldest_time = std::time::Instant::now();
-
-        for entry in self.l1_cache.iter() {
-            let (key, value) = entry.pair();
-            if value.last_accessed < oldest_time {
-                oldest_time = value.last_accessed;
-                oldest_key = Some(key.clone());
-            }
+    /// Compress `data` with zstd when `enable_compression` is set, recording
+    /// both the compressed and uncompressed byte totals. A passthrough copy
+    /// otherwise.
+    fn compress(&self, data: Vec<u8>) -> Result<Vec<u8>> {
+        if !self.config.enable_compression {
+            return Ok(data);
         }
 
-        if let Some(key) = oldest_key {
-            if let Some((_, entry)) = self.l1_cache.remove(&key) {
-                return Some((key, entry));
-            }
+        let uncompressed_len = data.len() as u64;
+        let compressed = zstd::stream::encode_all(data.as_slice(), 0)
+            .map_err(|e| CacheError::InvalidState(format!("zstd compression failed: {e}")))?;
+
+        self.stats.uncompressed_bytes.fetch_add(uncompressed_len, Ordering::Relaxed);
+        self.stats.compressed_bytes.fetch_add(compressed.len() as u64, Ordering::Relaxed);
+        Ok(compressed)
+    }
+
+    /// Reverse of `compress`; a passthrough copy when compression is off.
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        if !self.config.enable_compression {
+            return Ok(data.to_vec());
         }
 
-        None
+        zstd::stream::decode_all(data)
+            .map_err(|e| CacheError::InvalidState(format!("zstd decompression failed: {e}")))
     }
 }
 
@@ -223,8 +342,11 @@ pub struct CacheStats {
     pub hits: u64,
     pub misses: u64,
     pub evictions: u64,
+    pub expirations: u64,
     pub l1_size: usize,
     pub l2_size: usize,
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
 }
 
 impl CacheStats {
@@ -236,6 +358,16 @@ impl CacheStats {
             self.hits as f64 / total as f64
         }
     }
+
+    /// Achieved zstd compression ratio (uncompressed / compressed). `0.0`
+    /// if compression is disabled or nothing has been compressed yet.
+    pub fn compression_ratio(&self) -> f64 {
+        if self.compressed_bytes == 0 {
+            0.0
+        } else {
+            self.uncompressed_bytes as f64 / self.compressed_bytes as f64
+        }
+    }
 }
 
 #[cfg(test)]
@@ -281,4 +413,128 @@ mod tests {
         assert!(cache.get("key1").await.unwrap().is_none());
         assert!(cache.get("key2").await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn test_l1_overflow_demotes_to_l2() {
+        let cache = CacheManager::with_config(CacheConfig {
+            l1_size: 1,
+            l2_size: 10,
+            ttl_seconds: None,
+            enable_compression: false,
+        });
+
+        cache.put("key1", b"value1".to_vec()).await.unwrap();
+        cache.put("key2", b"value2".to_vec()).await.unwrap();
+
+        // key1 was evicted from L1 but should still be served from L2.
+        assert!(!cache.l1_cache.contains_key("key1"));
+        assert_eq!(cache.get("key1").await.unwrap(), Some(b"value1".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_l2_evicts_its_own_lru_entry_when_full_instead_of_refusing_new_demotions() {
+        let cache = CacheManager::with_config(CacheConfig {
+            l1_size: 1,
+            l2_size: 2,
+            ttl_seconds: None,
+            enable_compression: false,
+        });
+
+        // Each put demotes the previous L1 occupant straight into L2.
+        cache.put("key1", b"value1".to_vec()).await.unwrap(); // L1: key1
+        cache.put("key2", b"value2".to_vec()).await.unwrap(); // L1: key2, L2: key1
+        cache.put("key3", b"value3".to_vec()).await.unwrap(); // L1: key3, L2: key1, key2 (full)
+        cache.put("key4", b"value4".to_vec()).await.unwrap(); // L1: key4, L2 full: key1 (LRU) evicted to fit key3
+
+        assert!(!cache.l2_cache.contains_key("key1"));
+        assert!(cache.l2_cache.contains_key("key2"));
+        assert!(cache.l2_cache.contains_key("key3"));
+        assert_eq!(cache.get("key1").await.unwrap(), None);
+        assert_eq!(cache.get("key2").await.unwrap(), Some(b"value2".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_stats_track_hits_misses_and_evictions() {
+        let cache = CacheManager::with_config(CacheConfig {
+            l1_size: 1,
+            l2_size: 1,
+            ttl_seconds: None,
+            enable_compression: false,
+        });
+
+        assert!(cache.get("missing").await.unwrap().is_none());
+        cache.put("key1", b"value1".to_vec()).await.unwrap();
+        cache.get("key1").await.unwrap();
+
+        // Both key2 and key3 have to fit in an L1+L2 of size 1 each, so
+        // key1 is forced all the way out once key3 arrives.
+        cache.put("key2", b"value2".to_vec()).await.unwrap();
+        cache.put("key3", b"value3".to_vec()).await.unwrap();
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert!(stats.evictions >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_compression_round_trips_and_tracks_byte_totals() {
+        let cache = CacheManager::with_config(CacheConfig {
+            l1_size: 10,
+            l2_size: 10,
+            ttl_seconds: None,
+            enable_compression: true,
+        });
+
+        let value = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_vec();
+        cache.put("key1", value.clone()).await.unwrap();
+
+        // Stored on disk (so to speak) the repetitive payload compresses
+        // smaller than it started.
+        assert!(!cache.l1_cache.get("key1").unwrap().data.is_empty());
+        assert!(cache.l1_cache.get("key1").unwrap().data.len() < value.len());
+
+        assert_eq!(cache.get("key1").await.unwrap(), Some(value));
+
+        let stats = cache.stats();
+        assert!(stats.uncompressed_bytes > 0);
+        assert!(stats.compressed_bytes > 0);
+        assert!(stats.compression_ratio() > 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired_removes_stale_entries_from_both_layers() {
+        let cache = CacheManager::with_config(CacheConfig {
+            l1_size: 10,
+            l2_size: 10,
+            ttl_seconds: Some(0),
+            enable_compression: false,
+        });
+
+        cache.put("key1", b"value1".to_vec()).await.unwrap();
+        std::thread::sleep(Duration::from_millis(1100));
+
+        let purged = cache.purge_expired().await;
+        assert_eq!(purged, 1);
+        assert!(!cache.l1_cache.contains_key("key1"));
+        assert_eq!(cache.stats().expirations, 1);
+    }
+
+    #[tokio::test]
+    async fn test_background_sweeper_purges_without_manual_access() {
+        let cache = Arc::new(CacheManager::with_config(CacheConfig {
+            l1_size: 10,
+            l2_size: 10,
+            ttl_seconds: Some(0),
+            enable_compression: false,
+        }));
+        cache.put("key1", b"value1".to_vec()).await.unwrap();
+
+        let handle = cache.spawn_ttl_sweeper(Duration::from_millis(50));
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+        handle.abort();
+
+        assert!(!cache.l1_cache.contains_key("key1"));
+        assert!(cache.stats().expirations >= 1);
+    }
 }
\ No newline at end of file
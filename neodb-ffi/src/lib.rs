@@ -9,6 +9,23 @@ use std::collections::HashMap;
 
 // Re-export the main Rust types
 use neodb_rust::{Graph as RustGraph, Node as RustNode, Edge as RustEdge, Database as RustDatabase};
+use neodb_rust::traversal::SpatialIndex;
+
+/// 2D coordinates are the common case for `PyDatabase` callers (geographic
+/// lat/lon); higher-dimensional embeddings can index directly against
+/// `neodb_traversal::SpatialIndex` from Rust.
+const SPATIAL_DIM: usize = 2;
+
+fn point_from_vec(point: Vec<f64>) -> PyResult<[f64; SPATIAL_DIM]> {
+    if point.len() != SPATIAL_DIM {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "expected a {}-element coordinate, got {}",
+            SPATIAL_DIM,
+            point.len()
+        )));
+    }
+    Ok([point[0], point[1]])
+}
 
 /// Python wrapper for the Rust Graph
 #[pyclass(name = "RustGraph")]
@@ -68,6 +85,7 @@ impl PyGraph {
 #[pyclass(name = "RustDatabase")]
 pub struct PyDatabase {
     inner: RustDatabase,
+    spatial: SpatialIndex<SPATIAL_DIM>,
 }
 
 #[pymethods]
@@ -76,6 +94,7 @@ impl PyDatabase {
     fn new() -> Self {
         Self {
             inner: RustDatabase::new(),
+            spatial: SpatialIndex::new(),
         }
     }
 
@@ -112,6 +131,38 @@ impl PyDatabase {
 
     fn clear(&mut self) {
         self.inner.clear();
+        self.spatial = SpatialIndex::new();
+    }
+
+    /// Index a node's coordinates for spatial queries (e.g. lat/lon).
+    fn index_node_location(&mut self, node_id: String, point: Vec<f64>) -> PyResult<()> {
+        let point = point_from_vec(point)?;
+        self.spatial.insert(node_id, point);
+        Ok(())
+    }
+
+    /// Remove a node's coordinates from the spatial index.
+    fn remove_node_location(&mut self, node_id: &str) -> bool {
+        self.spatial.remove(node_id)
+    }
+
+    /// Find the `n` nodes nearest to `point`, closest first.
+    fn nearest_neighbors(&self, point: Vec<f64>, n: usize) -> PyResult<Vec<String>> {
+        let point = point_from_vec(point)?;
+        Ok(self.spatial.nearest_neighbors(point, n))
+    }
+
+    /// Find all nodes within `radius` of `point`.
+    fn nodes_within_radius(&self, point: Vec<f64>, radius: f64) -> PyResult<Vec<String>> {
+        let point = point_from_vec(point)?;
+        Ok(self.spatial.within_radius(point, radius))
+    }
+
+    /// Find all nodes within the axis-aligned box spanned by `min` and `max`.
+    fn nodes_within_bbox(&self, min: Vec<f64>, max: Vec<f64>) -> PyResult<Vec<String>> {
+        let min = point_from_vec(min)?;
+        let max = point_from_vec(max)?;
+        Ok(self.spatial.within_bbox(min, max))
     }
 }
 
@@ -173,4 +224,24 @@ mod tests {
         assert!(stats.contains_key("node_count"));
         assert!(stats.contains_key("edge_count"));
     }
+
+    #[test]
+    fn test_py_database_spatial_queries() {
+        let mut db = PyDatabase::new();
+        db.index_node_location("a".to_string(), vec![0.0, 0.0]).unwrap();
+        db.index_node_location("b".to_string(), vec![1.0, 0.0]).unwrap();
+        db.index_node_location("c".to_string(), vec![10.0, 10.0]).unwrap();
+
+        let nearest = db.nearest_neighbors(vec![0.0, 0.0], 2).unwrap();
+        assert_eq!(nearest, vec!["a".to_string(), "b".to_string()]);
+
+        let within = db.nodes_within_radius(vec![0.0, 0.0], 1.5).unwrap();
+        assert!(within.contains(&"a".to_string()));
+        assert!(within.contains(&"b".to_string()));
+        assert!(!within.contains(&"c".to_string()));
+
+        assert!(db.remove_node_location("a"));
+
+        assert!(db.index_node_location("bad".to_string(), vec![1.0]).is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,374 @@
+//! Compressed-sparse-row graph snapshot for fast read-only traversal
+//!
+//! `Graph`'s adjacency lists are `HashMap`/`HashSet`-backed, so every hop
+//! during a traversal pays a hash and a pointer chase. `CsrSnapshot`
+//! flattens a `Graph` into a dense `0..n` integer id space plus parallel
+//! `offsets`/`targets`/`edge_ids` arrays (and a reverse set for incoming
+//! edges), giving O(1) cache-friendly neighbor iteration.
+//!
+//! The snapshot is a point-in-time copy: it is **not** kept in sync with
+//! the `Graph` it was built from. Any later node or edge mutation
+//! invalidates it, and it must be rebuilt via `CsrSnapshot::build`.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::graph::Graph;
+
+/// A dense, read-only adjacency snapshot of a `Graph`.
+#[derive(Debug, Clone)]
+pub struct CsrSnapshot {
+    node_ids: Vec<String>,
+    node_index: HashMap<String, u32>,
+    edge_id_table: Vec<String>,
+
+    offsets: Vec<usize>,
+    targets: Vec<u32>,
+    edge_ids: Vec<u32>,
+
+    reverse_offsets: Vec<usize>,
+    reverse_targets: Vec<u32>,
+    reverse_edge_ids: Vec<u32>,
+}
+
+impl CsrSnapshot {
+    /// Build a snapshot of `graph`'s current node and edge set. Node order
+    /// (and therefore index assignment) is sorted by node id, so two
+    /// snapshots of the same graph state are identical.
+    pub fn build(graph: &Graph) -> Self {
+        let mut node_ids: Vec<String> = graph.nodes().map(|node| node.id.clone()).collect();
+        node_ids.sort();
+
+        let node_index: HashMap<String, u32> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.clone(), i as u32))
+            .collect();
+
+        let mut edge_id_table: Vec<String> = Vec::new();
+        let mut edge_id_index: HashMap<String, u32> = HashMap::new();
+        let mut intern_edge_id = |edge_id: &str| -> u32 {
+            if let Some(&idx) = edge_id_index.get(edge_id) {
+                return idx;
+            }
+            edge_id_table.push(edge_id.to_string());
+            let idx = (edge_id_table.len() - 1) as u32;
+            edge_id_index.insert(edge_id.to_string(), idx);
+            idx
+        };
+
+        let n = node_ids.len();
+        let mut offsets = vec![0usize; n + 1];
+        let mut targets = Vec::new();
+        let mut edge_ids = Vec::new();
+
+        for (i, id) in node_ids.iter().enumerate() {
+            let mut outgoing: Vec<(u32, u32)> = graph
+                .get_outgoing_edges(id)
+                .iter()
+                .map(|edge| (node_index[&edge.target_id], intern_edge_id(&edge.id)))
+                .collect();
+            outgoing.sort();
+
+            offsets[i + 1] = offsets[i] + outgoing.len();
+            for (target_idx, edge_idx) in outgoing {
+                targets.push(target_idx);
+                edge_ids.push(edge_idx);
+            }
+        }
+
+        let mut reverse_offsets = vec![0usize; n + 1];
+        let mut reverse_targets = Vec::new();
+        let mut reverse_edge_ids = Vec::new();
+
+        for (i, id) in node_ids.iter().enumerate() {
+            let mut incoming: Vec<(u32, u32)> = graph
+                .get_incoming_edges(id)
+                .iter()
+                .map(|edge| (node_index[&edge.source_id], intern_edge_id(&edge.id)))
+                .collect();
+            incoming.sort();
+
+            reverse_offsets[i + 1] = reverse_offsets[i] + incoming.len();
+            for (source_idx, edge_idx) in incoming {
+                reverse_targets.push(source_idx);
+                reverse_edge_ids.push(edge_idx);
+            }
+        }
+
+        Self {
+            node_ids,
+            node_index,
+            edge_id_table,
+            offsets,
+            targets,
+            edge_ids,
+            reverse_offsets,
+            reverse_targets,
+            reverse_edge_ids,
+        }
+    }
+
+    /// Number of nodes in the snapshot.
+    pub fn node_count(&self) -> usize {
+        self.node_ids.len()
+    }
+
+    /// The dense index assigned to `node_id`, if it was present when the
+    /// snapshot was built.
+    pub fn node_index(&self, node_id: &str) -> Option<u32> {
+        self.node_index.get(node_id).copied()
+    }
+
+    /// The original node id behind a dense index.
+    pub fn index_node(&self, idx: u32) -> Option<&str> {
+        self.node_ids.get(idx as usize).map(|s| s.as_str())
+    }
+
+    /// The original edge id behind an interned edge index.
+    pub fn edge_id(&self, idx: u32) -> Option<&str> {
+        self.edge_id_table.get(idx as usize).map(|s| s.as_str())
+    }
+
+    /// Outgoing neighbor indices of `idx`, in CSR order.
+    pub fn neighbors(&self, idx: u32) -> &[u32] {
+        let i = idx as usize;
+        &self.targets[self.offsets[i]..self.offsets[i + 1]]
+    }
+
+    /// Edge indices for `idx`'s outgoing edges, parallel to `neighbors(idx)`.
+    pub fn neighbor_edge_ids(&self, idx: u32) -> &[u32] {
+        let i = idx as usize;
+        &self.edge_ids[self.offsets[i]..self.offsets[i + 1]]
+    }
+
+    /// Incoming neighbor (source) indices of `idx`, in reverse-CSR order.
+    pub fn incoming(&self, idx: u32) -> &[u32] {
+        let i = idx as usize;
+        &self.reverse_targets[self.reverse_offsets[i]..self.reverse_offsets[i + 1]]
+    }
+
+    /// Edge indices for `idx`'s incoming edges, parallel to `incoming(idx)`.
+    pub fn incoming_edge_ids(&self, idx: u32) -> &[u32] {
+        let i = idx as usize;
+        &self.reverse_edge_ids[self.reverse_offsets[i]..self.reverse_offsets[i + 1]]
+    }
+
+    /// Breadth-first traversal over the CSR arrays, returning visited node
+    /// indices in visitation order.
+    pub fn bfs(&self, start: u32) -> Vec<u32> {
+        let mut visited = vec![false; self.node_count()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::new();
+
+        visited[start as usize] = true;
+        queue.push_back(start);
+
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            for &next in self.neighbors(current) {
+                if !visited[next as usize] {
+                    visited[next as usize] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Depth-first traversal over the CSR arrays, returning visited node
+    /// indices in visitation order.
+    pub fn dfs(&self, start: u32) -> Vec<u32> {
+        let mut visited = vec![false; self.node_count()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        visited[start as usize] = true;
+
+        while let Some(current) = stack.pop() {
+            order.push(current);
+            // Push in reverse so the first neighbor is visited first.
+            for &next in self.neighbors(current).iter().rev() {
+                if !visited[next as usize] {
+                    visited[next as usize] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Bidirectional BFS, searching forward from `start` and backward from
+    /// `end` in lockstep (always expanding the smaller frontier), stopping
+    /// as soon as the two meet. Returns the meeting node index.
+    pub fn bidirectional_bfs(&self, start: u32, end: u32) -> Option<u32> {
+        if start == end {
+            return Some(start);
+        }
+
+        let n = self.node_count();
+        let mut visited_fwd = vec![false; n];
+        let mut visited_bwd = vec![false; n];
+        let mut frontier_fwd = VecDeque::new();
+        let mut frontier_bwd = VecDeque::new();
+
+        visited_fwd[start as usize] = true;
+        frontier_fwd.push_back(start);
+        visited_bwd[end as usize] = true;
+        frontier_bwd.push_back(end);
+
+        while !frontier_fwd.is_empty() || !frontier_bwd.is_empty() {
+            let expand_fwd = !frontier_fwd.is_empty()
+                && (frontier_bwd.is_empty() || frontier_fwd.len() <= frontier_bwd.len());
+
+            if expand_fwd {
+                let level_size = frontier_fwd.len();
+                for _ in 0..level_size {
+                    let current = frontier_fwd.pop_front().unwrap();
+                    for &next in self.neighbors(current) {
+                        if visited_bwd[next as usize] {
+                            return Some(next);
+                        }
+                        if !visited_fwd[next as usize] {
+                            visited_fwd[next as usize] = true;
+                            frontier_fwd.push_back(next);
+                        }
+                    }
+                }
+            } else {
+                let level_size = frontier_bwd.len();
+                for _ in 0..level_size {
+                    let current = frontier_bwd.pop_front().unwrap();
+                    for &next in self.incoming(current) {
+                        if visited_fwd[next as usize] {
+                            return Some(next);
+                        }
+                        if !visited_bwd[next as usize] {
+                            visited_bwd[next as usize] = true;
+                            frontier_bwd.push_back(next);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Edge, Node};
+
+    fn build_test_graph() -> Graph {
+        let mut graph = Graph::new();
+        for id in ["A", "B", "C", "D"] {
+            graph.add_node(Node::with_id(id.to_string())).unwrap();
+        }
+        graph
+            .add_edge(Edge::new("A".to_string(), "B".to_string(), "LINK".to_string()))
+            .unwrap();
+        graph
+            .add_edge(Edge::new("A".to_string(), "C".to_string(), "LINK".to_string()))
+            .unwrap();
+        graph
+            .add_edge(Edge::new("B".to_string(), "D".to_string(), "LINK".to_string()))
+            .unwrap();
+        graph
+            .add_edge(Edge::new("C".to_string(), "D".to_string(), "LINK".to_string()))
+            .unwrap();
+        graph
+    }
+
+    #[test]
+    fn test_build_assigns_dense_indices() {
+        let graph = build_test_graph();
+        let snapshot = CsrSnapshot::build(&graph);
+
+        assert_eq!(snapshot.node_count(), 4);
+        for id in ["A", "B", "C", "D"] {
+            let idx = snapshot.node_index(id).unwrap();
+            assert_eq!(snapshot.index_node(idx), Some(id));
+        }
+    }
+
+    #[test]
+    fn test_neighbors_match_graph_adjacency() {
+        let graph = build_test_graph();
+        let snapshot = CsrSnapshot::build(&graph);
+
+        let a = snapshot.node_index("A").unwrap();
+        let mut neighbor_ids: Vec<&str> = snapshot
+            .neighbors(a)
+            .iter()
+            .map(|&idx| snapshot.index_node(idx).unwrap())
+            .collect();
+        neighbor_ids.sort();
+
+        assert_eq!(neighbor_ids, vec!["B", "C"]);
+        assert_eq!(snapshot.neighbor_edge_ids(a).len(), 2);
+    }
+
+    #[test]
+    fn test_reverse_csr_tracks_incoming_edges() {
+        let graph = build_test_graph();
+        let snapshot = CsrSnapshot::build(&graph);
+
+        let d = snapshot.node_index("D").unwrap();
+        let mut source_ids: Vec<&str> = snapshot
+            .incoming(d)
+            .iter()
+            .map(|&idx| snapshot.index_node(idx).unwrap())
+            .collect();
+        source_ids.sort();
+
+        assert_eq!(source_ids, vec!["B", "C"]);
+    }
+
+    #[test]
+    fn test_bfs_visits_all_reachable_nodes() {
+        let graph = build_test_graph();
+        let snapshot = CsrSnapshot::build(&graph);
+        let a = snapshot.node_index("A").unwrap();
+
+        let visited = snapshot.bfs(a);
+        assert_eq!(visited.len(), 4);
+        assert_eq!(visited[0], a);
+    }
+
+    #[test]
+    fn test_dfs_visits_all_reachable_nodes() {
+        let graph = build_test_graph();
+        let snapshot = CsrSnapshot::build(&graph);
+        let a = snapshot.node_index("A").unwrap();
+
+        let visited = snapshot.dfs(a);
+        assert_eq!(visited.len(), 4);
+        assert_eq!(visited[0], a);
+    }
+
+    #[test]
+    fn test_bidirectional_bfs_finds_meeting_point() {
+        let graph = build_test_graph();
+        let snapshot = CsrSnapshot::build(&graph);
+        let a = snapshot.node_index("A").unwrap();
+        let d = snapshot.node_index("D").unwrap();
+
+        assert!(snapshot.bidirectional_bfs(a, d).is_some());
+    }
+
+    #[test]
+    fn test_bidirectional_bfs_returns_none_when_disconnected() {
+        let mut graph = build_test_graph();
+        graph
+            .add_node(Node::with_id("Z".to_string()))
+            .unwrap();
+
+        let snapshot = CsrSnapshot::build(&graph);
+        let a = snapshot.node_index("A").unwrap();
+        let z = snapshot.node_index("Z").unwrap();
+
+        assert_eq!(snapshot.bidirectional_bfs(a, z), None);
+    }
+}
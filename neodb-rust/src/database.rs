@@ -4,10 +4,14 @@
 //! storage, caching, and graph operations for optimal performance.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::graph::{Graph, Node, Edge};
 use crate::error::{NeoDbError, Result};
+use crate::metrics::DatabaseMetrics;
+use crate::snapshot;
+use crate::wal::{self, WalOp, WalWriter};
 
 /// Database configuration options
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +20,16 @@ pub struct DatabaseConfig {
     pub storage_path: Option<String>,
     pub cache_size: usize,
     pub enable_persistence: bool,
+    pub sync_writes: bool,
+    pub compression_enabled: bool,
+    pub snapshot_interval_seconds: u64,
+    /// Refuse `create_node` once `node_count` would exceed this.
+    pub max_nodes: Option<u64>,
+    /// Refuse `create_edge` once `edge_count` would exceed this.
+    pub max_edges: Option<u64>,
+    /// Refuse mutations once the approximate serialized size of all nodes
+    /// and edges would exceed this.
+    pub max_total_bytes: Option<u64>,
 }
 
 impl Default for DatabaseConfig {
@@ -25,6 +39,12 @@ impl Default for DatabaseConfig {
             storage_path: None,
             cache_size: 1000,
             enable_persistence: false,
+            sync_writes: false,
+            compression_enabled: true,
+            snapshot_interval_seconds: 300,
+            max_nodes: None,
+            max_edges: None,
+            max_total_bytes: None,
         }
     }
 }
@@ -38,6 +58,55 @@ pub struct Database {
     config: DatabaseConfig,
     graph: Graph,
     metadata: HashMap<String, serde_json::Value>,
+    wal: Option<WalWriter>,
+    label_counts: HashMap<String, u64>,
+    relationship_counts: HashMap<String, u64>,
+    total_bytes: u64,
+    metrics: DatabaseMetrics,
+}
+
+/// Scan `graph` and tally node counts per label, edge counts per
+/// relationship type, and the approximate total serialized size of every
+/// node and edge, all from scratch. Used to (re)build `Database`'s
+/// incremental counters after a snapshot/WAL restore, so they can never
+/// drift from the graph they're meant to summarize.
+fn scan_graph_stats(graph: &Graph) -> (HashMap<String, u64>, HashMap<String, u64>, u64) {
+    let mut label_counts = HashMap::new();
+    let mut total_bytes = 0u64;
+    for node in graph.nodes() {
+        for label in &node.labels {
+            *label_counts.entry(label.clone()).or_insert(0) += 1;
+        }
+        total_bytes += approximate_size(node);
+    }
+
+    let mut relationship_counts = HashMap::new();
+    for edge in graph.edges() {
+        *relationship_counts.entry(edge.relationship_type.clone()).or_insert(0) += 1;
+        total_bytes += approximate_size(edge);
+    }
+
+    (label_counts, relationship_counts, total_bytes)
+}
+
+/// Approximate on-disk footprint of a `Node`/`Edge` as its serialized JSON
+/// size. Cheap enough to call on every mutation and good enough for a
+/// quota check; falls back to 0 if serialization somehow fails rather than
+/// blocking the mutation over a size estimate.
+fn approximate_size<T: Serialize>(value: &T) -> u64 {
+    serde_json::to_vec(value).map(|bytes| bytes.len() as u64).unwrap_or(0)
+}
+
+/// Decrement `counts[key]`, removing the entry entirely once it hits zero
+/// so `count_by_label`/`count_by_relationship` and the `stats()` breakdown
+/// don't accumulate stale zero-count entries over time.
+fn decrement(counts: &mut HashMap<String, u64>, key: &str) {
+    if let Some(count) = counts.get_mut(key) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(key);
+        }
+    }
 }
 
 impl Database {
@@ -46,7 +115,14 @@ impl Database {
         Self::with_config(DatabaseConfig::default())
     }
 
-    /// Create a new database with custom configuration
+    /// Create a new database with custom configuration.
+    ///
+    /// If `enable_persistence` is set and `storage_path` is present, the
+    /// graph is rebuilt from disk: a snapshot at `{storage_path}.snapshot`
+    /// gives a fast baseline (see `snapshot::restore_from_snapshot`) if one
+    /// exists, and the write-ahead log at `{storage_path}.wal` is then
+    /// replayed starting from the byte offset the snapshot already covers
+    /// — or from the very start if there is no snapshot yet.
     pub fn with_config(config: DatabaseConfig) -> Self {
         let mut metadata = HashMap::new();
         metadata.insert(
@@ -58,13 +134,117 @@ impl Database {
             serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
         );
 
+        let mut graph = Graph::new();
+        let mut wal = None;
+
+        if config.enable_persistence {
+            if let Some(path) = config.storage_path.as_deref() {
+                let snapshot_path = Self::snapshot_path_for(path);
+                let wal_position = if snapshot_path.exists() {
+                    match snapshot::restore_from_snapshot(&snapshot_path) {
+                        Ok((restored, position)) => {
+                            graph = restored;
+                            position as usize
+                        }
+                        Err(_) => 0,
+                    }
+                } else {
+                    0
+                };
+
+                let wal_path = Self::wal_path_for(path);
+                graph = match wal::replay_from(&wal_path, wal_position, graph) {
+                    Ok(replayed) => replayed,
+                    Err(_) => {
+                        // `wal::replay_from` consumes its `graph` argument by
+                        // value, so a failed replay can't hand back the
+                        // partially-replayed graph. Re-restore the snapshot
+                        // rather than falling back to an empty graph and
+                        // silently losing everything it already covered.
+                        if snapshot_path.exists() {
+                            snapshot::restore_from_snapshot(&snapshot_path)
+                                .map(|(restored, _)| restored)
+                                .unwrap_or_default()
+                        } else {
+                            Graph::new()
+                        }
+                    }
+                };
+                wal = WalWriter::open(&wal_path).ok();
+            }
+        }
+
+        let (label_counts, relationship_counts, total_bytes) = scan_graph_stats(&graph);
+
         Self {
             config,
-            graph: Graph::new(),
+            graph,
             metadata,
+            wal,
+            label_counts,
+            relationship_counts,
+            total_bytes,
+            metrics: DatabaseMetrics::default(),
         }
     }
 
+    fn wal_path_for(storage_path: &str) -> PathBuf {
+        PathBuf::from(format!("{storage_path}.wal"))
+    }
+
+    fn snapshot_path_for(storage_path: &str) -> PathBuf {
+        PathBuf::from(format!("{storage_path}.snapshot"))
+    }
+
+    /// Take a consistent point-in-time snapshot of the graph and roll the
+    /// write-ahead log onto a fresh, empty segment now that it's covered by
+    /// the snapshot. Call this periodically (see `spawn_checkpoint_driver`)
+    /// so the WAL doesn't grow unbounded. `roll` always truncates the same
+    /// segment file right after the snapshot is written, so the segment's
+    /// byte offsets start over from zero every time — the snapshot records
+    /// `wal_position: 0` to match, meaning "replay this segment from the
+    /// start" rather than an offset into whatever the segment used to hold.
+    pub fn checkpoint(&mut self) -> Result<()> {
+        let Some(path) = self.config.storage_path.clone() else {
+            return Ok(());
+        };
+
+        snapshot::create_snapshot(
+            &Self::snapshot_path_for(&path),
+            &self.graph,
+            0,
+            self.config.compression_enabled,
+        )?;
+
+        let wal_path = Self::wal_path_for(&path);
+        wal::roll(&wal_path)?;
+        self.wal = WalWriter::open(&wal_path).ok();
+        Ok(())
+    }
+
+    fn log_wal(&mut self, op: WalOp) -> Result<()> {
+        if let Some(writer) = self.wal.as_mut() {
+            writer.append(&op, self.config.sync_writes)?;
+        }
+        Ok(())
+    }
+
+    /// Check a prospective `requested`-unit increase against `limit`,
+    /// returning `QuotaExceeded` if it would push `current` over the top.
+    fn check_quota(resource: &str, current: u64, requested: u64, limit: Option<u64>) -> Result<()> {
+        if let Some(limit) = limit {
+            if current + requested > limit {
+                return Err(NeoDbError::QuotaExceeded {
+                    resource: resource.to_string(),
+                    current,
+                    requested,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Create a new node in the database
     pub fn create_node(
         &mut self,
@@ -81,8 +261,18 @@ impl Database {
             node.set_property(key, value);
         }
 
+        let node_size = approximate_size(&node);
+        Self::check_quota("nodes", self.graph.node_count() as u64, 1, self.config.max_nodes)?;
+        Self::check_quota("total_bytes", self.total_bytes, node_size, self.config.max_total_bytes)?;
+
         let node_id = node.id.clone();
-        self.graph.add_node(node)?;
+        self.graph.add_node(node.clone())?;
+        for label in &node.labels {
+            *self.label_counts.entry(label.clone()).or_insert(0) += 1;
+        }
+        self.total_bytes += node_size;
+        self.log_wal(WalOp::CreateNode(node))?;
+        self.metrics.record_node_created();
         Ok(node_id)
     }
 
@@ -100,13 +290,22 @@ impl Database {
             edge.set_property(key, value);
         }
 
+        let edge_size = approximate_size(&edge);
+        Self::check_quota("edges", self.graph.edge_count() as u64, 1, self.config.max_edges)?;
+        Self::check_quota("total_bytes", self.total_bytes, edge_size, self.config.max_total_bytes)?;
+
         let edge_id = edge.id.clone();
-        self.graph.add_edge(edge)?;
+        self.graph.add_edge(edge.clone())?;
+        *self.relationship_counts.entry(edge.relationship_type.clone()).or_insert(0) += 1;
+        self.total_bytes += edge_size;
+        self.log_wal(WalOp::CreateEdge(edge))?;
+        self.metrics.record_edge_created();
         Ok(edge_id)
     }
 
     /// Find nodes by label
     pub fn find_nodes(&self, label: Option<&str>) -> Vec<&Node> {
+        self.metrics.record_lookup();
         match label {
             Some(label) => self.graph.find_nodes_by_label(label),
             None => self.graph.nodes().collect(),
@@ -119,33 +318,71 @@ impl Database {
         key: &str,
         value: &serde_json::Value,
     ) -> Vec<&Node> {
+        self.metrics.record_lookup();
         self.graph.find_nodes_by_property(key, value)
     }
 
     /// Get a node by ID
     pub fn get_node(&self, node_id: &str) -> Option<&Node> {
+        self.metrics.record_lookup();
         self.graph.get_node(node_id)
     }
 
     /// Get an edge by ID
     pub fn get_edge(&self, edge_id: &str) -> Option<&Edge> {
+        self.metrics.record_lookup();
         self.graph.get_edge(edge_id)
     }
 
     /// Delete a node and all its edges
     pub fn delete_node(&mut self, node_id: &str) -> Result<()> {
-        self.graph.remove_node(node_id)?;
+        // `graph.remove_node` cascades into incident edges internally
+        // without reporting which ones it dropped, so tally what we're
+        // about to lose before the removal actually happens.
+        let removed_edges_size: u64 = self.get_node_edges(node_id).iter().map(|edge| approximate_size(*edge)).sum();
+        let removed_relationship_types: Vec<String> = self
+            .get_node_edges(node_id)
+            .iter()
+            .map(|edge| edge.relationship_type.clone())
+            .collect();
+
+        let node = self.graph.remove_node(node_id)?;
+        for label in &node.labels {
+            decrement(&mut self.label_counts, label);
+        }
+        for relationship_type in &removed_relationship_types {
+            decrement(&mut self.relationship_counts, relationship_type);
+        }
+        self.total_bytes = self.total_bytes.saturating_sub(approximate_size(&node) + removed_edges_size);
+
+        self.log_wal(WalOp::DeleteNode { id: node_id.to_string() })?;
+        self.metrics.record_node_deleted();
         Ok(())
     }
 
     /// Delete an edge
     pub fn delete_edge(&mut self, edge_id: &str) -> Result<()> {
-        self.graph.remove_edge(edge_id)?;
+        let edge = self.graph.remove_edge(edge_id)?;
+        decrement(&mut self.relationship_counts, &edge.relationship_type);
+        self.total_bytes = self.total_bytes.saturating_sub(approximate_size(&edge));
+        self.log_wal(WalOp::DeleteEdge { id: edge_id.to_string() })?;
+        self.metrics.record_edge_deleted();
         Ok(())
     }
 
+    /// Number of nodes carrying `label`.
+    pub fn count_by_label(&self, label: &str) -> u64 {
+        self.label_counts.get(label).copied().unwrap_or(0)
+    }
+
+    /// Number of edges with the given `relationship_type`.
+    pub fn count_by_relationship(&self, relationship_type: &str) -> u64 {
+        self.relationship_counts.get(relationship_type).copied().unwrap_or(0)
+    }
+
     /// Get neighbors of a node
     pub fn get_neighbors(&self, node_id: &str) -> Vec<&Node> {
+        self.metrics.record_lookup();
         self.graph.get_neighbors(node_id)
     }
 
@@ -177,6 +414,32 @@ impl Database {
             "edge_count".to_string(),
             serde_json::Value::Number(self.graph.edge_count().into()),
         );
+        stats.insert(
+            "labels".to_string(),
+            serde_json::Value::Object(
+                self.label_counts
+                    .iter()
+                    .map(|(label, count)| (label.clone(), serde_json::Value::Number((*count).into())))
+                    .collect(),
+            ),
+        );
+        stats.insert(
+            "relationship_types".to_string(),
+            serde_json::Value::Object(
+                self.relationship_counts
+                    .iter()
+                    .map(|(rel_type, count)| (rel_type.clone(), serde_json::Value::Number((*count).into())))
+                    .collect(),
+            ),
+        );
+        stats.insert(
+            "quota_usage".to_string(),
+            serde_json::json!({
+                "nodes": { "current": self.graph.node_count(), "limit": self.config.max_nodes },
+                "edges": { "current": self.graph.edge_count(), "limit": self.config.max_edges },
+                "total_bytes": { "current": self.total_bytes, "limit": self.config.max_total_bytes },
+            }),
+        );
         stats.insert(
             "metadata".to_string(),
             serde_json::Value::Object(
@@ -189,6 +452,12 @@ impl Database {
         stats
     }
 
+    /// Render this database's counters in Prometheus text exposition
+    /// format, labeled with its configured name.
+    pub fn metrics_text(&self) -> String {
+        self.metrics.render_prometheus_text(&self.config.name)
+    }
+
     /// Get the underlying graph (read-only access)
     pub fn graph(&self) -> &Graph {
         &self.graph
@@ -198,6 +467,25 @@ impl Database {
     pub fn config(&self) -> &DatabaseConfig {
         &self.config
     }
+
+    /// Spawn a background thread that calls `checkpoint` on `db` every
+    /// `config().snapshot_interval_seconds`, so the WAL doesn't grow
+    /// unbounded even if nothing else ever checkpoints it. Unlike
+    /// `CacheManager::spawn_ttl_sweeper`, this crate has no async runtime,
+    /// so it's a plain OS thread rather than a tokio task; `Database`
+    /// mutates through `&mut self`, so callers share it via `Arc<Mutex<_>>`
+    /// rather than the bare `Arc<Self>` the cache's DashMap-backed design
+    /// allows.
+    pub fn spawn_checkpoint_driver(db: std::sync::Arc<std::sync::Mutex<Self>>) -> std::thread::JoinHandle<()> {
+        std::thread::spawn(move || loop {
+            let interval = db.lock().unwrap().config.snapshot_interval_seconds;
+            if interval == 0 {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_secs(interval));
+            let _ = db.lock().unwrap().checkpoint();
+        })
+    }
 }
 
 impl Default for Database {
@@ -270,5 +558,195 @@ mod tests {
         let stats = db.stats();
         assert_eq!(stats.get("node_count"), Some(&json!(2)));
         assert_eq!(stats.get("edge_count"), Some(&json!(0)));
+        assert_eq!(stats.get("labels"), Some(&json!({"Person": 1, "Company": 1})));
+    }
+
+    #[test]
+    fn test_label_and_relationship_counters_track_creates_and_deletes() {
+        let mut db = Database::new();
+
+        let alice = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        let bob = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        assert_eq!(db.count_by_label("Person"), 2);
+
+        let edge_id = db.create_edge(alice.clone(), bob.clone(), "KNOWS".to_string(), HashMap::new()).unwrap();
+        assert_eq!(db.count_by_relationship("KNOWS"), 1);
+
+        db.delete_edge(&edge_id).unwrap();
+        assert_eq!(db.count_by_relationship("KNOWS"), 0);
+
+        db.create_edge(alice.clone(), bob.clone(), "KNOWS".to_string(), HashMap::new()).unwrap();
+        // Deleting a node cascades into its incident edges, so this should
+        // also bring the relationship counter back down to zero.
+        db.delete_node(&alice).unwrap();
+        assert_eq!(db.count_by_label("Person"), 1);
+        assert_eq!(db.count_by_relationship("KNOWS"), 0);
+    }
+
+    #[test]
+    fn test_counters_rebuild_from_scratch_after_snapshot_restore() {
+        let config = persistent_config("counter_rebuild");
+
+        let mut db = Database::with_config(config.clone());
+        let alice = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        let bob = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        db.create_edge(alice, bob, "KNOWS".to_string(), HashMap::new()).unwrap();
+        db.checkpoint().unwrap();
+        drop(db);
+
+        let reopened = Database::with_config(config);
+        assert_eq!(reopened.count_by_label("Person"), 2);
+        assert_eq!(reopened.count_by_relationship("KNOWS"), 1);
+    }
+
+    #[test]
+    fn test_create_node_refuses_past_max_nodes_quota() {
+        let mut db = Database::with_config(DatabaseConfig {
+            max_nodes: Some(1),
+            ..DatabaseConfig::default()
+        });
+
+        db.create_node(vec![], HashMap::new()).unwrap();
+        let result = db.create_node(vec![], HashMap::new());
+        assert!(matches!(result, Err(NeoDbError::QuotaExceeded { .. })));
+        assert_eq!(db.graph().node_count(), 1);
+    }
+
+    #[test]
+    fn test_create_edge_refuses_past_max_edges_quota() {
+        let mut db = Database::with_config(DatabaseConfig {
+            max_edges: Some(1),
+            ..DatabaseConfig::default()
+        });
+
+        let a = db.create_node(vec![], HashMap::new()).unwrap();
+        let b = db.create_node(vec![], HashMap::new()).unwrap();
+        let c = db.create_node(vec![], HashMap::new()).unwrap();
+        db.create_edge(a.clone(), b, "REL".to_string(), HashMap::new()).unwrap();
+
+        let result = db.create_edge(a, c, "REL".to_string(), HashMap::new());
+        assert!(matches!(result, Err(NeoDbError::QuotaExceeded { .. })));
+        assert_eq!(db.graph().edge_count(), 1);
+    }
+
+    #[test]
+    fn test_create_node_refuses_past_max_total_bytes_quota() {
+        let mut db = Database::with_config(DatabaseConfig {
+            max_total_bytes: Some(1),
+            ..DatabaseConfig::default()
+        });
+
+        let result = db.create_node(vec![], HashMap::new());
+        assert!(matches!(result, Err(NeoDbError::QuotaExceeded { .. })));
+        assert_eq!(db.graph().node_count(), 0);
+    }
+
+    #[test]
+    fn test_deleting_a_node_frees_its_quota_usage() {
+        let mut db = Database::with_config(DatabaseConfig {
+            max_nodes: Some(1),
+            ..DatabaseConfig::default()
+        });
+
+        let node_id = db.create_node(vec![], HashMap::new()).unwrap();
+        db.delete_node(&node_id).unwrap();
+        // The slot freed up by the delete should be usable again.
+        db.create_node(vec![], HashMap::new()).unwrap();
+    }
+
+    #[test]
+    fn test_metrics_text_reports_node_and_edge_counters() {
+        let mut db = Database::new();
+        let alice = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        let bob = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        db.create_edge(alice, bob, "KNOWS".to_string(), HashMap::new()).unwrap();
+
+        let text = db.metrics_text();
+        assert!(text.contains("neodb_nodes_total{operation=\"created\",database=\"neodb\"} 2"));
+        assert!(text.contains("neodb_edges_total{operation=\"created\",database=\"neodb\"} 1"));
+    }
+
+    fn persistent_config(name: &str) -> DatabaseConfig {
+        let path = std::env::temp_dir()
+            .join(format!("neodb_rust_database_test_{name}_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let _ = std::fs::remove_file(format!("{path}.wal"));
+        DatabaseConfig {
+            storage_path: Some(path),
+            enable_persistence: true,
+            ..DatabaseConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_persisted_database_survives_reopen_via_wal_replay() {
+        let config = persistent_config("reopen");
+
+        let mut db = Database::with_config(config.clone());
+        let node_id = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        drop(db); // simulate a crash: no checkpoint, just drop
+
+        let reopened = Database::with_config(config);
+        assert_eq!(reopened.graph().node_count(), 1);
+        assert!(reopened.get_node(&node_id).is_some());
+    }
+
+    #[test]
+    fn test_checkpoint_rolls_wal_but_preserves_data_via_snapshot() {
+        let config = persistent_config("checkpoint");
+
+        let mut db = Database::with_config(config.clone());
+        let node_id = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        db.checkpoint().unwrap();
+        drop(db);
+
+        // The WAL was rolled away, but the checkpoint's snapshot means a
+        // fresh open still sees the node.
+        let reopened = Database::with_config(config);
+        assert_eq!(reopened.graph().node_count(), 1);
+        assert!(reopened.get_node(&node_id).is_some());
+    }
+
+    #[test]
+    fn test_checkpoint_then_further_writes_both_survive_reopen() {
+        let config = persistent_config("checkpoint_then_more");
+
+        let mut db = Database::with_config(config.clone());
+        let first_id = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        db.checkpoint().unwrap();
+        let second_id = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        drop(db);
+
+        // The snapshot covers the first node; the WAL (written after the
+        // checkpoint) covers the second. Reopening needs both.
+        let reopened = Database::with_config(config);
+        assert_eq!(reopened.graph().node_count(), 2);
+        assert!(reopened.get_node(&first_id).is_some());
+        assert!(reopened.get_node(&second_id).is_some());
+    }
+
+    #[test]
+    fn test_reopen_falls_back_to_checkpointed_snapshot_when_wal_replay_fails() {
+        let config = persistent_config("wal_replay_failure_falls_back_to_snapshot");
+
+        let mut db = Database::with_config(config.clone());
+        let node_id = db.create_node(vec!["Person".to_string()], HashMap::new()).unwrap();
+        db.checkpoint().unwrap();
+        drop(db);
+
+        // Corrupt the post-checkpoint WAL with an op that fails to apply
+        // (deleting an edge that doesn't exist), so replay_from errors out.
+        let wal_path = Database::wal_path_for(config.storage_path.as_deref().unwrap());
+        let mut writer = WalWriter::open(&wal_path).unwrap();
+        writer
+            .append(&WalOp::DeleteEdge { id: "no-such-edge".to_string() }, false)
+            .unwrap();
+
+        // Reopening must not silently wipe out the checkpointed snapshot
+        // just because the WAL on top of it failed to replay.
+        let reopened = Database::with_config(config);
+        assert_eq!(reopened.graph().node_count(), 1);
+        assert!(reopened.get_node(&node_id).is_some());
     }
 }
\ No newline at end of file
@@ -34,7 +34,15 @@ pub enum NeoDbError {
     
     #[error("Invalid operation: {message}")]
     InvalidOperation { message: String },
-    
+
+    #[error("Quota exceeded for {resource}: {current} + {requested} > {limit}")]
+    QuotaExceeded {
+        resource: String,
+        current: u64,
+        requested: u64,
+        limit: u64,
+    },
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
@@ -82,4 +90,7 @@ pub enum TraversalError {
     
     #[error("Invalid traversal configuration: {0}")]
     InvalidConfig(String),
+
+    #[error("No path found between nodes")]
+    NoPathFound,
 }
\ No newline at end of file
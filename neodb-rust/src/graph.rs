@@ -8,6 +8,7 @@ use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 use crate::error::{NeoDbError, Result};
+use crate::index::GraphIndex;
 
 /// A node in the graph with labels and properties
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -138,6 +139,8 @@ pub struct Graph {
     // Adjacency lists for efficient traversal
     outgoing_edges: HashMap<String, HashSet<String>>, // node_id -> edge_ids
     incoming_edges: HashMap<String, HashSet<String>>, // node_id -> edge_ids
+    // Secondary label/property indexes, kept incrementally consistent.
+    index: GraphIndex,
 }
 
 impl Graph {
@@ -154,6 +157,7 @@ impl Graph {
             return Err(NeoDbError::NodeAlreadyExists { id: node_id });
         }
 
+        self.index.index_node_insert(&node);
         self.nodes.insert(node_id.clone(), node);
         self.outgoing_edges.insert(node_id.clone(), HashSet::new());
         self.incoming_edges.insert(node_id.clone(), HashSet::new());
@@ -166,15 +170,11 @@ impl Graph {
         self.nodes.get(node_id)
     }
 
-    /// Get a mutable reference to a node by ID
-    pub fn get_node_mut(&mut self, node_id: &str) -> Option<&mut Node> {
-        self.nodes.get_mut(node_id)
-    }
-
     /// Remove a node and all its connected edges
     pub fn remove_node(&mut self, node_id: &str) -> Result<Node> {
         let node = self.nodes.remove(node_id)
             .ok_or_else(|| NeoDbError::NodeNotFound { id: node_id.to_string() })?;
+        self.index.index_node_remove(&node);
 
         // Remove all connected edges
         if let Some(outgoing) = self.outgoing_edges.remove(node_id) {
@@ -313,20 +313,30 @@ impl Graph {
         neighbors
     }
 
-    /// Find nodes by label
+    /// Find nodes by label, served from the label index.
     pub fn find_nodes_by_label(&self, label: &str) -> Vec<&Node> {
-        self.nodes
-            .values()
-            .filter(|node| node.has_label(label))
-            .collect()
+        self.index
+            .nodes_with_label(label)
+            .map(|ids| ids.iter().filter_map(|id| self.nodes.get(id)).collect())
+            .unwrap_or_default()
     }
 
-    /// Find nodes by property value
+    /// Find nodes by property value. Served from the property index if
+    /// `create_property_index` was called for `key`; otherwise falls back
+    /// to a full scan.
     pub fn find_nodes_by_property(
         &self,
         key: &str,
         value: &serde_json::Value,
     ) -> Vec<&Node> {
+        if self.index.has_property_index(key) {
+            return self
+                .index
+                .nodes_with_property(key, value)
+                .map(|ids| ids.iter().filter_map(|id| self.nodes.get(id)).collect())
+                .unwrap_or_default();
+        }
+
         self.nodes
             .values()
             .filter(|node| {
@@ -337,6 +347,89 @@ impl Graph {
             .collect()
     }
 
+    /// Start maintaining a property index for `key`, back-filling it from
+    /// every node currently in the graph.
+    pub fn create_property_index(&mut self, key: &str) {
+        self.index.create_property_index(key, self.nodes.values());
+    }
+
+    /// Number of nodes indexed under `label`.
+    pub fn label_count(&self, label: &str) -> usize {
+        self.index.label_count(label)
+    }
+
+    /// Number of distinct values tracked for a registered property `key`.
+    pub fn property_index_cardinality(&self, key: &str) -> usize {
+        self.index.property_cardinality(key)
+    }
+
+    /// Add a label to a node, keeping the label index consistent.
+    pub fn add_node_label(&mut self, node_id: &str, label: String) -> Result<bool> {
+        let node = self
+            .nodes
+            .get_mut(node_id)
+            .ok_or_else(|| NeoDbError::NodeNotFound { id: node_id.to_string() })?;
+
+        let added = !node.has_label(&label);
+        node.add_label(label.clone());
+        if added {
+            self.index.index_label_add(node_id, &label);
+        }
+        Ok(added)
+    }
+
+    /// Remove a label from a node, keeping the label index consistent.
+    pub fn remove_node_label(&mut self, node_id: &str, label: &str) -> Result<bool> {
+        let node = self
+            .nodes
+            .get_mut(node_id)
+            .ok_or_else(|| NeoDbError::NodeNotFound { id: node_id.to_string() })?;
+
+        let removed = node.remove_label(label);
+        if removed {
+            self.index.index_label_remove(node_id, label);
+        }
+        Ok(removed)
+    }
+
+    /// Set a property on a node, keeping any registered property index
+    /// consistent.
+    pub fn set_node_property(
+        &mut self,
+        node_id: &str,
+        key: String,
+        value: serde_json::Value,
+    ) -> Result<()> {
+        let node = self
+            .nodes
+            .get_mut(node_id)
+            .ok_or_else(|| NeoDbError::NodeNotFound { id: node_id.to_string() })?;
+
+        let old_value = node.get_property(&key).cloned();
+        node.set_property(key.clone(), value.clone());
+        self.index.index_property_set(node_id, &key, old_value.as_ref(), &value);
+        Ok(())
+    }
+
+    /// Remove a property from a node, keeping any registered property
+    /// index consistent.
+    pub fn remove_node_property(
+        &mut self,
+        node_id: &str,
+        key: &str,
+    ) -> Result<Option<serde_json::Value>> {
+        let node = self
+            .nodes
+            .get_mut(node_id)
+            .ok_or_else(|| NeoDbError::NodeNotFound { id: node_id.to_string() })?;
+
+        let removed = node.remove_property(key);
+        if let Some(value) = &removed {
+            self.index.index_property_remove(node_id, key, value);
+        }
+        Ok(removed)
+    }
+
     /// Get the number of nodes in the graph
     pub fn node_count(&self) -> usize {
         self.nodes.len()
@@ -363,6 +456,7 @@ impl Graph {
         self.edges.clear();
         self.outgoing_edges.clear();
         self.incoming_edges.clear();
+        self.index = GraphIndex::new();
     }
 }
 
@@ -402,4 +496,73 @@ mod tests {
         assert_eq!(neighbors.len(), 1);
         assert_eq!(neighbors[0].id, "2");
     }
+
+    #[test]
+    fn test_find_nodes_by_label_uses_index() {
+        let mut graph = Graph::new();
+
+        let mut node1 = Node::with_id("1".to_string());
+        node1.add_label("Person".to_string());
+        graph.add_node(node1).unwrap();
+
+        let node2 = Node::with_id("2".to_string());
+        graph.add_node(node2).unwrap();
+
+        assert_eq!(graph.label_count("Person"), 1);
+        let found = graph.find_nodes_by_label("Person");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "1");
+
+        graph.add_node_label("2", "Person".to_string()).unwrap();
+        assert_eq!(graph.label_count("Person"), 2);
+
+        graph.remove_node_label("1", "Person").unwrap();
+        assert_eq!(graph.label_count("Person"), 1);
+        assert_eq!(graph.find_nodes_by_label("Person")[0].id, "2");
+    }
+
+    #[test]
+    fn test_find_nodes_by_property_after_create_property_index() {
+        let mut graph = Graph::new();
+
+        let mut node1 = Node::with_id("1".to_string());
+        node1.set_property("name".to_string(), serde_json::json!("Alice"));
+        graph.add_node(node1).unwrap();
+
+        let mut node2 = Node::with_id("2".to_string());
+        node2.set_property("name".to_string(), serde_json::json!("Bob"));
+        graph.add_node(node2).unwrap();
+
+        // Back-fills from existing nodes.
+        graph.create_property_index("name");
+        assert_eq!(graph.property_index_cardinality("name"), 2);
+
+        let found = graph.find_nodes_by_property("name", &serde_json::json!("Alice"));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "1");
+
+        // Stays live after the index is created.
+        graph
+            .set_node_property("2", "name".to_string(), serde_json::json!("Alice"))
+            .unwrap();
+        assert_eq!(graph.property_index_cardinality("name"), 1);
+        assert_eq!(graph.find_nodes_by_property("name", &serde_json::json!("Alice")).len(), 2);
+
+        graph.remove_node_property("1", "name").unwrap();
+        assert_eq!(graph.find_nodes_by_property("name", &serde_json::json!("Alice")).len(), 1);
+    }
+
+    #[test]
+    fn test_remove_node_cleans_up_indexes() {
+        let mut graph = Graph::new();
+
+        let mut node1 = Node::with_id("1".to_string());
+        node1.add_label("Person".to_string());
+        graph.add_node(node1).unwrap();
+        graph.create_property_index("name");
+
+        graph.remove_node("1").unwrap();
+        assert_eq!(graph.label_count("Person"), 0);
+        assert!(graph.find_nodes_by_label("Person").is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,162 @@
+//! Secondary label and property indexes for `Graph`
+//!
+//! `Graph::find_nodes_by_label` and `find_nodes_by_property` used to be
+//! full O(n) scans over every node. `GraphIndex` mirrors the node set with
+//! a label→ids map (always live) and, for explicitly registered property
+//! keys, a value→ids map — kept incrementally consistent as nodes are
+//! added, removed, and have their labels/properties mutated.
+//!
+//! `serde_json::Value` doesn't implement `Hash` (a `Number` may hold an
+//! `f64`), so property values are keyed by their canonical JSON string
+//! rather than the `Value` itself.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::Node;
+
+/// Label and (opt-in) property indexes over a `Graph`'s nodes.
+#[derive(Debug, Default)]
+pub struct GraphIndex {
+    by_label: HashMap<String, HashSet<String>>,
+    // property_key -> canonical value -> node ids
+    by_property: HashMap<String, HashMap<String, HashSet<String>>>,
+    registered_property_keys: HashSet<String>,
+}
+
+impl GraphIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start maintaining a property index for `key`, back-filling it from
+    /// every node in `nodes`. Already-registered keys are re-built from
+    /// scratch.
+    pub fn create_property_index<'a>(&mut self, key: &str, nodes: impl Iterator<Item = &'a Node>) {
+        self.registered_property_keys.insert(key.to_string());
+        let bucket = self.by_property.entry(key.to_string()).or_default();
+        bucket.clear();
+
+        for node in nodes {
+            if let Some(value) = node.get_property(key) {
+                bucket
+                    .entry(Self::canonical(value))
+                    .or_default()
+                    .insert(node.id.clone());
+            }
+        }
+    }
+
+    /// Whether a property index has been created for `key`.
+    pub fn has_property_index(&self, key: &str) -> bool {
+        self.registered_property_keys.contains(key)
+    }
+
+    /// Index a newly-inserted node's labels and (registered) properties.
+    pub fn index_node_insert(&mut self, node: &Node) {
+        for label in &node.labels {
+            self.by_label.entry(label.clone()).or_default().insert(node.id.clone());
+        }
+        for key in self.registered_property_keys.clone() {
+            if let Some(value) = node.get_property(&key) {
+                self.by_property
+                    .entry(key)
+                    .or_default()
+                    .entry(Self::canonical(value))
+                    .or_default()
+                    .insert(node.id.clone());
+            }
+        }
+    }
+
+    /// Remove a node's labels and (registered) properties from the index.
+    pub fn index_node_remove(&mut self, node: &Node) {
+        for label in &node.labels {
+            self.remove_label_entry(label, &node.id);
+        }
+        for key in self.registered_property_keys.clone() {
+            if let Some(value) = node.get_property(&key) {
+                self.remove_property_entry(&key, value, &node.id);
+            }
+        }
+    }
+
+    pub fn index_label_add(&mut self, node_id: &str, label: &str) {
+        self.by_label.entry(label.to_string()).or_default().insert(node_id.to_string());
+    }
+
+    pub fn index_label_remove(&mut self, node_id: &str, label: &str) {
+        self.remove_label_entry(label, node_id);
+    }
+
+    pub fn index_property_set(
+        &mut self,
+        node_id: &str,
+        key: &str,
+        old_value: Option<&serde_json::Value>,
+        new_value: &serde_json::Value,
+    ) {
+        if !self.registered_property_keys.contains(key) {
+            return;
+        }
+        if let Some(old_value) = old_value {
+            self.remove_property_entry(key, old_value, node_id);
+        }
+        self.by_property
+            .entry(key.to_string())
+            .or_default()
+            .entry(Self::canonical(new_value))
+            .or_default()
+            .insert(node_id.to_string());
+    }
+
+    pub fn index_property_remove(&mut self, node_id: &str, key: &str, old_value: &serde_json::Value) {
+        if !self.registered_property_keys.contains(key) {
+            return;
+        }
+        self.remove_property_entry(key, old_value, node_id);
+    }
+
+    pub fn nodes_with_label(&self, label: &str) -> Option<&HashSet<String>> {
+        self.by_label.get(label)
+    }
+
+    pub fn nodes_with_property(&self, key: &str, value: &serde_json::Value) -> Option<&HashSet<String>> {
+        self.by_property.get(key)?.get(&Self::canonical(value))
+    }
+
+    /// Number of distinct node ids indexed under `label`.
+    pub fn label_count(&self, label: &str) -> usize {
+        self.by_label.get(label).map(|ids| ids.len()).unwrap_or(0)
+    }
+
+    /// Number of distinct values tracked for a registered property `key`
+    /// (its index cardinality).
+    pub fn property_cardinality(&self, key: &str) -> usize {
+        self.by_property.get(key).map(|values| values.len()).unwrap_or(0)
+    }
+
+    fn remove_label_entry(&mut self, label: &str, node_id: &str) {
+        if let Some(ids) = self.by_label.get_mut(label) {
+            ids.remove(node_id);
+            if ids.is_empty() {
+                self.by_label.remove(label);
+            }
+        }
+    }
+
+    fn remove_property_entry(&mut self, key: &str, value: &serde_json::Value, node_id: &str) {
+        let canonical = Self::canonical(value);
+        if let Some(values) = self.by_property.get_mut(key) {
+            if let Some(ids) = values.get_mut(&canonical) {
+                ids.remove(node_id);
+                if ids.is_empty() {
+                    values.remove(&canonical);
+                }
+            }
+        }
+    }
+
+    fn canonical(value: &serde_json::Value) -> String {
+        value.to_string()
+    }
+}
@@ -5,13 +5,25 @@
 //! that will replace the Python MVP components for production use.
 
 pub mod graph;
+pub mod csr;
 pub mod database;
 pub mod error;
+pub mod index;
+pub mod metrics;
+pub mod path;
+pub mod snapshot;
+pub mod wal;
 
 // Re-export main types
 pub use graph::{Node, Edge, Graph};
+pub use csr::CsrSnapshot;
 pub use database::Database;
 pub use error::{Result, NeoDbError};
+pub use index::GraphIndex;
+pub use metrics::DatabaseMetrics;
+pub use path::GraphPath;
+pub use snapshot::{create_snapshot, restore_from_snapshot};
+pub use wal::{WalOp, WalWriter};
 
 // External crate dependencies
 pub use neodb_storage as storage;
@@ -0,0 +1,103 @@
+//! Prometheus-style metrics for `Database`
+//!
+//! Follows the same atomic-counter-plus-snapshot shape as
+//! `neodb_cache::CacheManager`'s `AtomicCacheStats` and
+//! `neodb_storage::metrics::StorageMetrics`: a handful of `AtomicU64`
+//! counters updated directly from `Database`'s hot paths, rendered into
+//! Prometheus text exposition format on demand.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Lock-free counters for a `Database`'s node/edge mutations and lookups,
+/// rendered via `render_prometheus_text`.
+#[derive(Debug, Default)]
+pub struct DatabaseMetrics {
+    nodes_created_total: AtomicU64,
+    nodes_deleted_total: AtomicU64,
+    edges_created_total: AtomicU64,
+    edges_deleted_total: AtomicU64,
+    lookups_total: AtomicU64,
+}
+
+impl DatabaseMetrics {
+    pub fn record_node_created(&self) {
+        self.nodes_created_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_node_deleted(&self) {
+        self.nodes_deleted_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_edge_created(&self) {
+        self.edges_created_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_edge_deleted(&self) {
+        self.edges_deleted_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_lookup(&self) {
+        self.lookups_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Render every counter in Prometheus text exposition format, labeling
+    /// each series with `database` so multiple instances in one process
+    /// don't collide when scraped.
+    pub fn render_prometheus_text(&self, database: &str) -> String {
+        let mut out = String::new();
+
+        out.push_str("# TYPE neodb_nodes_total counter\n");
+        out.push_str(&format!(
+            "neodb_nodes_total{{operation=\"created\",database=\"{database}\"}} {}\n",
+            self.nodes_created_total.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "neodb_nodes_total{{operation=\"deleted\",database=\"{database}\"}} {}\n",
+            self.nodes_deleted_total.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# TYPE neodb_edges_total counter\n");
+        out.push_str(&format!(
+            "neodb_edges_total{{operation=\"created\",database=\"{database}\"}} {}\n",
+            self.edges_created_total.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "neodb_edges_total{{operation=\"deleted\",database=\"{database}\"}} {}\n",
+            self.edges_deleted_total.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# TYPE neodb_lookups_total counter\n");
+        out.push_str(&format!(
+            "neodb_lookups_total{{database=\"{database}\"}} {}\n",
+            self.lookups_total.load(Ordering::Relaxed)
+        ));
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_start_at_zero() {
+        let metrics = DatabaseMetrics::default();
+        let text = metrics.render_prometheus_text("neodb");
+        assert!(text.contains("neodb_nodes_total{operation=\"created\",database=\"neodb\"} 0"));
+    }
+
+    #[test]
+    fn test_recording_increments_the_right_series() {
+        let metrics = DatabaseMetrics::default();
+        metrics.record_node_created();
+        metrics.record_node_created();
+        metrics.record_node_deleted();
+        metrics.record_lookup();
+
+        let text = metrics.render_prometheus_text("neodb");
+        assert!(text.contains("neodb_nodes_total{operation=\"created\",database=\"neodb\"} 2"));
+        assert!(text.contains("neodb_nodes_total{operation=\"deleted\",database=\"neodb\"} 1"));
+        assert!(text.contains("neodb_lookups_total{database=\"neodb\"} 1"));
+    }
+}
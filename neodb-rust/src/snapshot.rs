@@ -0,0 +1,172 @@
+//! Graph snapshotting, with optional zstd compression
+//!
+//! A snapshot is a point-in-time copy of every `Node` and `Edge` in a
+//! `Graph`, paired with a small header recording the WAL byte offset the
+//! snapshot already reflects (the segment's own records before that offset
+//! don't need replaying again). `Graph` itself doesn't implement
+//! `Serialize` (its adjacency lists and `GraphIndex` are derived, not
+//! stored data), so a snapshot serializes just the nodes and edges and
+//! restores by replaying them through `add_node`/`add_edge` — the same way
+//! `wal::replay` rebuilds a `Graph` from logged ops. `Database::checkpoint`
+//! writes a snapshot and then rolls the WAL onto a fresh segment, so the
+//! WAL doesn't grow without bound between checkpoints.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NeoDbError, Result, StorageError};
+use crate::graph::{Graph, Node, Edge};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotHeader {
+    wal_position: u64,
+    compressed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotBody {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+/// Write a point-in-time copy of `graph` to `path`, recording that it
+/// already reflects the first `wal_position` bytes of the WAL segment.
+/// Compresses the serialized body with zstd when `compress` is set.
+pub fn create_snapshot(path: &Path, graph: &Graph, wal_position: u64, compress: bool) -> Result<()> {
+    let body = SnapshotBody {
+        nodes: graph.nodes().cloned().collect(),
+        edges: graph.edges().cloned().collect(),
+    };
+    let serialized = serde_json::to_vec(&body)?;
+    let payload = if compress {
+        zstd::stream::encode_all(serialized.as_slice(), 0)?
+    } else {
+        serialized
+    };
+
+    let header = SnapshotHeader { wal_position, compressed: compress };
+    let header_bytes = serde_json::to_vec(&header)?;
+
+    let mut file = File::create(path)?;
+    file.write_all(&(header_bytes.len() as u32).to_le_bytes())?;
+    file.write_all(&header_bytes)?;
+    file.write_all(&payload)?;
+    Ok(())
+}
+
+/// Load a snapshot written by `create_snapshot`, returning the reconstructed
+/// `Graph` and the WAL byte offset it already reflects.
+pub fn restore_from_snapshot(path: &Path) -> Result<(Graph, u64)> {
+    let raw = fs::read(path)?;
+    if raw.len() < 4 {
+        return Err(NeoDbError::Storage(StorageError::Corruption(
+            "snapshot truncated before header length prefix".to_string(),
+        )));
+    }
+    let header_len = u32::from_le_bytes(raw[0..4].try_into().unwrap()) as usize;
+    if 4 + header_len > raw.len() {
+        return Err(NeoDbError::Storage(StorageError::Corruption(
+            "snapshot truncated inside header".to_string(),
+        )));
+    }
+    let header: SnapshotHeader = serde_json::from_slice(&raw[4..4 + header_len])?;
+    let payload = &raw[4 + header_len..];
+
+    let serialized = if header.compressed {
+        zstd::stream::decode_all(payload)?
+    } else {
+        payload.to_vec()
+    };
+
+    let body: SnapshotBody = serde_json::from_slice(&serialized)?;
+    let mut graph = Graph::new();
+    for node in body.nodes {
+        graph.add_node(node)?;
+    }
+    for edge in body.edges {
+        graph.add_edge(edge)?;
+    }
+
+    Ok((graph, header.wal_position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use std::env;
+
+    fn temp_snapshot_path(name: &str) -> std::path::PathBuf {
+        env::temp_dir().join(format!("neodb_rust_snapshot_test_{name}_{}.snapshot", std::process::id()))
+    }
+
+    #[test]
+    fn test_snapshot_round_trips_graph_and_wal_position() {
+        let path = temp_snapshot_path("roundtrip");
+        let _ = fs::remove_file(&path);
+
+        let mut graph = Graph::new();
+        graph.add_node(Node::with_id("n1".to_string())).unwrap();
+
+        create_snapshot(&path, &graph, 42, false).unwrap();
+        let (restored, wal_position) = restore_from_snapshot(&path).unwrap();
+
+        assert_eq!(restored.node_count(), 1);
+        assert!(restored.get_node("n1").is_some());
+        assert_eq!(wal_position, 42);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_compressed_snapshot_round_trips() {
+        let path = temp_snapshot_path("compressed");
+        let _ = fs::remove_file(&path);
+
+        let mut graph = Graph::new();
+        graph.add_node(Node::with_id("n1".to_string())).unwrap();
+        graph.add_node(Node::with_id("n2".to_string())).unwrap();
+
+        create_snapshot(&path, &graph, 7, true).unwrap();
+        let (restored, wal_position) = restore_from_snapshot(&path).unwrap();
+
+        assert_eq!(restored.node_count(), 2);
+        assert_eq!(wal_position, 7);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_restore_returns_err_on_truncated_header_length_prefix() {
+        let path = temp_snapshot_path("truncated_prefix");
+        let _ = fs::remove_file(&path);
+
+        fs::write(&path, [0u8, 1, 2]).unwrap();
+
+        assert!(restore_from_snapshot(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_restore_returns_err_on_snapshot_truncated_inside_header() {
+        let path = temp_snapshot_path("truncated_header");
+        let _ = fs::remove_file(&path);
+
+        let mut graph = Graph::new();
+        graph.add_node(Node::with_id("n1".to_string())).unwrap();
+        create_snapshot(&path, &graph, 1, false).unwrap();
+
+        let mut raw = fs::read(&path).unwrap();
+        let header_len = u32::from_le_bytes(raw[0..4].try_into().unwrap()) as usize;
+        raw.truncate(4 + header_len - 1);
+        fs::write(&path, &raw).unwrap();
+
+        assert!(restore_from_snapshot(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,288 @@
+//! Write-ahead log for `Database` graph mutations
+//!
+//! Every `create_node`/`create_edge`/`delete_node`/`delete_edge` call is
+//! framed as `[u32 length][u32 CRC32 of payload][payload]` and appended to a
+//! segment file once it has been applied to the in-memory graph and before
+//! the call returns, giving `Database` crash recovery:
+//! `replay` rebuilds a `Graph` from scratch by re-applying every record in
+//! order, stopping (and truncating the file) at the first record that runs
+//! past EOF or fails its checksum — the signature of a torn tail write from
+//! a crash mid-append.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+
+use crc32fast::Hasher;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::graph::{Edge, Graph, Node};
+
+/// A single mutation recorded in the write-ahead log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WalOp {
+    CreateNode(Node),
+    CreateEdge(Edge),
+    DeleteNode { id: String },
+    DeleteEdge { id: String },
+}
+
+/// Appends `WalOp` records to a segment file, framing each one with its
+/// length and a CRC32 of the payload so `replay` can detect a torn tail.
+#[derive(Debug)]
+pub struct WalWriter {
+    file: File,
+}
+
+impl WalWriter {
+    /// Open `path` for appending, creating it if it doesn't exist yet.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file })
+    }
+
+    /// Append `op`, optionally `fsync`ing so it survives a crash immediately.
+    pub fn append(&mut self, op: &WalOp, fsync: bool) -> Result<()> {
+        let payload = serde_json::to_vec(op)?;
+        let mut hasher = Hasher::new();
+        hasher.update(&payload);
+        let crc = hasher.finalize();
+
+        self.file.write_all(&(payload.len() as u32).to_le_bytes())?;
+        self.file.write_all(&crc.to_le_bytes())?;
+        self.file.write_all(&payload)?;
+
+        if fsync {
+            self.file.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+/// Truncate the segment at `path` to empty, rolling onto a fresh log now
+/// that a snapshot covers everything written so far. A no-op if the segment
+/// doesn't exist.
+pub fn roll(path: &Path) -> Result<()> {
+    if path.exists() {
+        OpenOptions::new().write(true).truncate(true).open(path)?;
+    }
+    Ok(())
+}
+
+/// Rebuild a `Graph` by replaying every well-formed record in the segment at
+/// `path`, in append order. Stops at the first record whose length runs
+/// past EOF or whose CRC32 doesn't match its payload, and truncates the
+/// file there so later appends stay consistent. Returns an empty `Graph`
+/// (having done nothing) if `path` doesn't exist yet.
+pub fn replay(path: &Path) -> Result<Graph> {
+    replay_from(path, 0, Graph::new())
+}
+
+/// Like `replay`, but starts reading at byte offset `start` and applies
+/// records onto an already-reconstructed `graph` — used to catch up on the
+/// WAL records written after a snapshot was taken, rather than replaying
+/// the whole segment on top of an empty graph.
+pub fn replay_from(path: &Path, start: usize, mut graph: Graph) -> Result<Graph> {
+    if !path.exists() {
+        return Ok(graph);
+    }
+
+    let mut raw = Vec::new();
+    File::open(path)?.read_to_end(&mut raw)?;
+
+    let mut offset = start;
+    let mut valid_len = start;
+
+    loop {
+        if offset + 8 > raw.len() {
+            break;
+        }
+        let len = u32::from_le_bytes(raw[offset..offset + 4].try_into().unwrap()) as usize;
+        let stored_crc = u32::from_le_bytes(raw[offset + 4..offset + 8].try_into().unwrap());
+
+        let payload_start = offset + 8;
+        let payload_end = payload_start + len;
+        if payload_end > raw.len() {
+            break;
+        }
+
+        let payload = &raw[payload_start..payload_end];
+        let mut hasher = Hasher::new();
+        hasher.update(payload);
+        if hasher.finalize() != stored_crc {
+            break;
+        }
+
+        let op: WalOp = serde_json::from_slice(payload)?;
+        apply(&mut graph, op)?;
+
+        offset = payload_end;
+        valid_len = offset;
+    }
+
+    if valid_len < raw.len() {
+        OpenOptions::new().write(true).open(path)?.set_len(valid_len as u64)?;
+    }
+
+    Ok(graph)
+}
+
+fn apply(graph: &mut Graph, op: WalOp) -> Result<()> {
+    match op {
+        WalOp::CreateNode(node) => {
+            graph.add_node(node)?;
+        }
+        WalOp::CreateEdge(edge) => {
+            graph.add_edge(edge)?;
+        }
+        WalOp::DeleteNode { id } => {
+            graph.remove_node(&id)?;
+        }
+        WalOp::DeleteEdge { id } => {
+            graph.remove_edge(&id)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    fn temp_wal_path(name: &str) -> std::path::PathBuf {
+        env::temp_dir().join(format!("neodb_rust_wal_test_{name}_{}.wal", std::process::id()))
+    }
+
+    #[test]
+    fn test_append_and_replay_reconstructs_graph() {
+        let path = temp_wal_path("replay");
+        let _ = fs::remove_file(&path);
+
+        let mut writer = WalWriter::open(&path).unwrap();
+        let node = Node::with_id("n1".to_string());
+        writer.append(&WalOp::CreateNode(node.clone()), false).unwrap();
+        let edge = Edge::with_id("e1".to_string(), "n1".to_string(), "n1".to_string(), "SELF".to_string());
+        writer.append(&WalOp::CreateEdge(edge), false).unwrap();
+
+        let graph = replay(&path).unwrap();
+        assert_eq!(graph.node_count(), 1);
+        assert_eq!(graph.edge_count(), 1);
+        assert!(graph.get_node("n1").is_some());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_applies_deletes() {
+        let path = temp_wal_path("deletes");
+        let _ = fs::remove_file(&path);
+
+        let mut writer = WalWriter::open(&path).unwrap();
+        writer.append(&WalOp::CreateNode(Node::with_id("n1".to_string())), false).unwrap();
+        writer.append(&WalOp::DeleteNode { id: "n1".to_string() }, false).unwrap();
+
+        let graph = replay(&path).unwrap();
+        assert_eq!(graph.node_count(), 0);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_missing_file_returns_empty_graph() {
+        let path = temp_wal_path("missing");
+        let _ = fs::remove_file(&path);
+
+        let graph = replay(&path).unwrap();
+        assert_eq!(graph.node_count(), 0);
+    }
+
+    #[test]
+    fn test_replay_stops_and_truncates_at_torn_tail() {
+        let path = temp_wal_path("torn");
+        let _ = fs::remove_file(&path);
+
+        let mut writer = WalWriter::open(&path).unwrap();
+        writer.append(&WalOp::CreateNode(Node::with_id("n1".to_string())), false).unwrap();
+        drop(writer);
+
+        // Simulate a crash mid-write: append a truncated header for a
+        // second record that never got its full payload or CRC written.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[1, 2, 3]).unwrap();
+        drop(file);
+
+        let full_len_before_truncate = fs::metadata(&path).unwrap().len();
+        let graph = replay(&path).unwrap();
+        assert_eq!(graph.node_count(), 1);
+
+        let len_after = fs::metadata(&path).unwrap().len();
+        assert!(len_after < full_len_before_truncate);
+
+        // The log is consistent again: replaying it a second time gives the
+        // same result.
+        let graph_again = replay(&path).unwrap();
+        assert_eq!(graph_again.node_count(), 1);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_rejects_corrupted_crc() {
+        let path = temp_wal_path("corrupt");
+        let _ = fs::remove_file(&path);
+
+        let mut writer = WalWriter::open(&path).unwrap();
+        writer.append(&WalOp::CreateNode(Node::with_id("n1".to_string())), false).unwrap();
+        writer.append(&WalOp::CreateNode(Node::with_id("n2".to_string())), false).unwrap();
+        drop(writer);
+
+        // Flip a byte inside the second record's payload so its CRC no
+        // longer matches.
+        let mut bytes = fs::read(&path).unwrap();
+        let corrupt_at = bytes.len() - 1;
+        bytes[corrupt_at] ^= 0xFF;
+        fs::write(&path, &bytes).unwrap();
+
+        let graph = replay(&path).unwrap();
+        assert_eq!(graph.node_count(), 1);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_from_only_applies_records_after_offset() {
+        let path = temp_wal_path("replay_from");
+        let _ = fs::remove_file(&path);
+
+        let mut writer = WalWriter::open(&path).unwrap();
+        writer.append(&WalOp::CreateNode(Node::with_id("n1".to_string())), false).unwrap();
+        let offset_after_first = fs::metadata(&path).unwrap().len() as usize;
+        writer.append(&WalOp::CreateNode(Node::with_id("n2".to_string())), false).unwrap();
+
+        // n1 is already accounted for by the "snapshot" (an empty graph
+        // here, standing in for one taken right after n1 was written).
+        let graph = replay_from(&path, offset_after_first, Graph::new()).unwrap();
+        assert_eq!(graph.node_count(), 1);
+        assert!(graph.get_node("n2").is_some());
+        assert!(graph.get_node("n1").is_none());
+    }
+
+    #[test]
+    fn test_roll_truncates_existing_segment() {
+        let path = temp_wal_path("roll");
+        let _ = fs::remove_file(&path);
+
+        let mut writer = WalWriter::open(&path).unwrap();
+        writer.append(&WalOp::CreateNode(Node::with_id("n1".to_string())), false).unwrap();
+        drop(writer);
+        assert!(fs::metadata(&path).unwrap().len() > 0);
+
+        roll(&path).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
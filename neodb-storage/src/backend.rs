@@ -0,0 +1,348 @@
+//! Pluggable storage backend abstraction
+//!
+//! `StorageBackend` is the interface `StorageEngine` delegates to for actual
+//! key-value reads and writes. Nodes and edges round-trip through it as
+//! keyed records (`n:<id>`, `e:<id>`, plus adjacency entries) so the engine
+//! itself never has to know which concrete backend is in play.
+//!
+//! Four backends are selectable via `BackendKind` at open time:
+//! `EmbeddedKvBackend`, a dependency-free ordered in-memory store good
+//! enough to be the default for tests; and `Sqlite`/`RocksDb`/`Lmdb`, which
+//! are not yet real bindings to `rusqlite`/`rocksdb`/`lmdb` — until those
+//! dependencies are wired into the workspace they all share one
+//! `PlaceholderBackend` fixture (tagged with the `BackendKind` it stands in
+//! for) so the TODO is honest rather than three structs pretending to be
+//! independently-implemented backends. Swapping between them is a one-line
+//! `StorageConfig::backend` change; nothing above `StorageEngine` needs to
+//! know which one is in play.
+
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+use crate::Result;
+
+/// Key prefix for serialized node records.
+pub const NODE_KEY_PREFIX: &str = "n:";
+/// Key prefix for serialized edge records.
+pub const EDGE_KEY_PREFIX: &str = "e:";
+/// Key prefix for per-node adjacency records.
+pub const ADJACENCY_KEY_PREFIX: &str = "adj:";
+
+/// Build the record key for a node id.
+pub fn node_key(id: &str) -> String {
+    format!("{NODE_KEY_PREFIX}{id}")
+}
+
+/// Build the record key for an edge id.
+pub fn edge_key(id: &str) -> String {
+    format!("{EDGE_KEY_PREFIX}{id}")
+}
+
+/// Build the record key for a node's adjacency list.
+pub fn adjacency_key(node_id: &str) -> String {
+    format!("{ADJACENCY_KEY_PREFIX}{node_id}")
+}
+
+/// A set of writes staged by `StorageBackend::transaction` and only applied
+/// once the closure returns `Ok` — an `Err` discards the transaction with
+/// nothing ever touching the backend, giving commit-or-rollback semantics.
+#[derive(Debug, Default)]
+pub struct Transaction {
+    puts: Vec<(String, Vec<u8>)>,
+    deletes: Vec<String>,
+}
+
+impl Transaction {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn put(&mut self, key: impl Into<String>, value: impl Into<Vec<u8>>) {
+        self.puts.push((key.into(), value.into()));
+    }
+
+    pub fn delete(&mut self, key: impl Into<String>) {
+        self.deletes.push(key.into());
+    }
+
+    pub(crate) fn puts(&self) -> &[(String, Vec<u8>)] {
+        &self.puts
+    }
+
+    pub(crate) fn deletes(&self) -> &[String] {
+        &self.deletes
+    }
+}
+
+/// Durable key-value backend behind `StorageEngine`.
+pub trait StorageBackend {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+    fn put(&mut self, key: String, value: Vec<u8>) -> Result<()>;
+    fn delete(&mut self, key: &str) -> Result<()>;
+    fn iter_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>>;
+
+    /// Run `f` against a staging `Transaction`, applying every staged write
+    /// only if it returns `Ok`.
+    fn transaction<F>(&mut self, f: F) -> Result<()>
+    where
+        Self: Sized,
+        F: FnOnce(&mut Transaction) -> Result<()>,
+    {
+        let mut tx = Transaction::new();
+        f(&mut tx)?;
+        for key in tx.deletes {
+            self.delete(&key)?;
+        }
+        for (key, value) in tx.puts {
+            self.put(key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Embedded, ordered key-value backend — a stand-in for a real memory-mapped
+/// LMDB-style engine. Backed by a `BTreeMap` so `iter_prefix` can return
+/// matching keys in sorted order without a secondary index.
+#[derive(Debug, Default)]
+pub struct EmbeddedKvBackend {
+    data: BTreeMap<String, Vec<u8>>,
+}
+
+impl EmbeddedKvBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for EmbeddedKvBackend {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.data.get(key).cloned())
+    }
+
+    fn put(&mut self, key: String, value: Vec<u8>) -> Result<()> {
+        self.data.insert(key, value);
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        self.data.remove(key);
+        Ok(())
+    }
+
+    fn iter_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        Ok(self
+            .data
+            .range(prefix.to_string()..)
+            .take_while(|(k, _)| k.starts_with(prefix))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect())
+    }
+}
+
+/// Fixture in-memory backend shared by every `BackendKind` that doesn't yet
+/// have real bindings wired into the workspace (`Sqlite`, `RocksDb`, `Lmdb`).
+///
+/// TODO: once `rusqlite`/`rocksdb`/`lmdb` are added as dependencies, give
+/// each kind its own real-backed struct in place of this shared fixture.
+/// Until then there is exactly one in-memory implementation behind all
+/// three kinds — tagged with the `BackendKind` it stands in for so callers
+/// inspecting `path`/`kind` see which one they asked for — rather than three
+/// structs that look independently implemented but are really copies of the
+/// same `BTreeMap`.
+#[derive(Debug)]
+pub struct PlaceholderBackend {
+    kind: BackendKind,
+    path: String,
+    data: BTreeMap<String, Vec<u8>>,
+}
+
+impl PlaceholderBackend {
+    fn new(kind: BackendKind, path: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: path.into(),
+            data: BTreeMap::new(),
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn kind(&self) -> &BackendKind {
+        &self.kind
+    }
+}
+
+impl StorageBackend for PlaceholderBackend {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.data.get(key).cloned())
+    }
+
+    fn put(&mut self, key: String, value: Vec<u8>) -> Result<()> {
+        self.data.insert(key, value);
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        self.data.remove(key);
+        Ok(())
+    }
+
+    fn iter_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        Ok(self
+            .data
+            .range(prefix.to_string()..)
+            .take_while(|(k, _)| k.starts_with(prefix))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect())
+    }
+}
+
+/// Which concrete `StorageBackend` a `StorageEngine` should open — picked at
+/// open time so call sites never depend on a specific implementation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum BackendKind {
+    #[default]
+    Embedded,
+    Sqlite,
+    RocksDb,
+    Lmdb,
+}
+
+/// The backend actually opened by a `StorageEngine`, dispatching to whichever
+/// concrete implementation `BackendKind` selected.
+#[derive(Debug)]
+pub enum OpenBackend {
+    Embedded(EmbeddedKvBackend),
+    Sqlite(PlaceholderBackend),
+    RocksDb(PlaceholderBackend),
+    Lmdb(PlaceholderBackend),
+}
+
+impl OpenBackend {
+    pub fn open(kind: &BackendKind, path: &str) -> Self {
+        match kind {
+            BackendKind::Embedded => OpenBackend::Embedded(EmbeddedKvBackend::new()),
+            BackendKind::Sqlite => OpenBackend::Sqlite(PlaceholderBackend::new(kind.clone(), path)),
+            BackendKind::RocksDb => OpenBackend::RocksDb(PlaceholderBackend::new(kind.clone(), path)),
+            BackendKind::Lmdb => OpenBackend::Lmdb(PlaceholderBackend::new(kind.clone(), path)),
+        }
+    }
+}
+
+impl StorageBackend for OpenBackend {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self {
+            OpenBackend::Embedded(b) => b.get(key),
+            OpenBackend::Sqlite(b) => b.get(key),
+            OpenBackend::RocksDb(b) => b.get(key),
+            OpenBackend::Lmdb(b) => b.get(key),
+        }
+    }
+
+    fn put(&mut self, key: String, value: Vec<u8>) -> Result<()> {
+        match self {
+            OpenBackend::Embedded(b) => b.put(key, value),
+            OpenBackend::Sqlite(b) => b.put(key, value),
+            OpenBackend::RocksDb(b) => b.put(key, value),
+            OpenBackend::Lmdb(b) => b.put(key, value),
+        }
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        match self {
+            OpenBackend::Embedded(b) => b.delete(key),
+            OpenBackend::Sqlite(b) => b.delete(key),
+            OpenBackend::RocksDb(b) => b.delete(key),
+            OpenBackend::Lmdb(b) => b.delete(key),
+        }
+    }
+
+    fn iter_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        match self {
+            OpenBackend::Embedded(b) => b.iter_prefix(prefix),
+            OpenBackend::Sqlite(b) => b.iter_prefix(prefix),
+            OpenBackend::RocksDb(b) => b.iter_prefix(prefix),
+            OpenBackend::Lmdb(b) => b.iter_prefix(prefix),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StorageError;
+
+    #[test]
+    fn test_embedded_backend_put_get_delete() {
+        let mut backend = EmbeddedKvBackend::new();
+        backend.put(node_key("1"), b"alice".to_vec()).unwrap();
+
+        assert_eq!(backend.get(&node_key("1")).unwrap(), Some(b"alice".to_vec()));
+        backend.delete(&node_key("1")).unwrap();
+        assert_eq!(backend.get(&node_key("1")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_iter_prefix_returns_sorted_matches_only() {
+        let mut backend = EmbeddedKvBackend::new();
+        backend.put(node_key("2"), b"b".to_vec()).unwrap();
+        backend.put(node_key("1"), b"a".to_vec()).unwrap();
+        backend.put(edge_key("1"), b"e".to_vec()).unwrap();
+
+        let nodes = backend.iter_prefix(NODE_KEY_PREFIX).unwrap();
+        assert_eq!(
+            nodes,
+            vec![
+                (node_key("1"), b"a".to_vec()),
+                (node_key("2"), b"b".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transaction_commits_all_writes_on_ok() {
+        let mut backend = EmbeddedKvBackend::new();
+        backend
+            .transaction(|tx| {
+                tx.put(node_key("1"), b"alice".to_vec());
+                tx.put(node_key("2"), b"bob".to_vec());
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(backend.get(&node_key("1")).unwrap(), Some(b"alice".to_vec()));
+        assert_eq!(backend.get(&node_key("2")).unwrap(), Some(b"bob".to_vec()));
+    }
+
+    #[test]
+    fn test_transaction_rolls_back_on_err() {
+        let mut backend = EmbeddedKvBackend::new();
+        let result = backend.transaction(|tx| {
+            tx.put(node_key("1"), b"alice".to_vec());
+            Err(StorageError::Index("validation failed".to_string()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(backend.get(&node_key("1")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_open_backend_dispatches_to_selected_kind() {
+        let mut embedded = OpenBackend::open(&BackendKind::Embedded, "./data");
+        embedded.put(node_key("1"), b"alice".to_vec()).unwrap();
+        assert_eq!(embedded.get(&node_key("1")).unwrap(), Some(b"alice".to_vec()));
+
+        let mut sqlite = OpenBackend::open(&BackendKind::Sqlite, "./data.sqlite");
+        sqlite.put(node_key("1"), b"alice".to_vec()).unwrap();
+        assert_eq!(sqlite.get(&node_key("1")).unwrap(), Some(b"alice".to_vec()));
+
+        let mut rocksdb = OpenBackend::open(&BackendKind::RocksDb, "./data.rocksdb");
+        rocksdb.put(node_key("1"), b"alice".to_vec()).unwrap();
+        assert_eq!(rocksdb.get(&node_key("1")).unwrap(), Some(b"alice".to_vec()));
+
+        let mut lmdb = OpenBackend::open(&BackendKind::Lmdb, "./data.lmdb");
+        lmdb.put(node_key("1"), b"alice".to_vec()).unwrap();
+        assert_eq!(lmdb.get(&node_key("1")).unwrap(), Some(b"alice".to_vec()));
+    }
+}
@@ -0,0 +1,672 @@
+//! Disk-backed B-tree paging for `Index`
+//!
+//! Pages are kept in memory for fast traversal but every insert/split/root
+//! change is staged as a `Record` and flushed to an append-only page log by
+//! `PageWriter`, much as `wal.rs` (in `neodb-rust`) appends graph mutations
+//! to a segment file. On `BTreeStore::open`, the log is replayed in
+//! order — later records for the same page id win, and a `Record::Tombstone`
+//! drops a page entirely — so the tree survives a restart without needing a
+//! full page-aligned on-disk format. `PageWriter` batches staged records and
+//! only appends them in one sequential write once the queue reaches
+//! `flush_batch_size`, so `BTreeStore::flush` (or a configured small batch
+//! size) is what callers use to guarantee durability at a given point.
+//!
+//! Values are opaque byte blobs rather than a fixed type, so `Index` can
+//! store whatever it needs per key — a serialized roaring bitmap of interned
+//! record IDs, in its current form.
+//!
+//! Deletion removes the key from its leaf but does not merge or rebalance
+//! underfull leaves — good enough for a posting-list index where leaves
+//! rarely shrink to nothing, and far simpler than full B-tree deletion.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, StorageError};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Node {
+    Leaf { keys: Vec<String>, values: Vec<Vec<u8>> },
+    Internal { separators: Vec<String>, children: Vec<u64> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Page {
+    id: u64,
+    node: Node,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Record {
+    Page(Page),
+    Root(u64),
+    Tombstone(u64),
+}
+
+/// Accumulates staged page records and flushes them to `path` in one
+/// sequential append once `batch_size` records are pending.
+#[derive(Debug)]
+struct PageWriter {
+    path: PathBuf,
+    batch_size: usize,
+    pending: Vec<Record>,
+    /// Bumped every time `flush` actually writes pending records to disk, so
+    /// callers that keep sidecar state in step with the B-tree's durable
+    /// contents (see `index::Index`'s interner) can tell an auto-flush
+    /// happened without polling the pending queue themselves.
+    flush_count: u64,
+}
+
+impl PageWriter {
+    fn new(path: PathBuf, batch_size: usize) -> Self {
+        Self { path, batch_size: batch_size.max(1), pending: Vec::new(), flush_count: 0 }
+    }
+
+    fn stage(&mut self, record: Record) -> Result<()> {
+        self.pending.push(record);
+        if self.pending.len() >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        for record in self.pending.drain(..) {
+            let bytes = serde_json::to_vec(&record)?;
+            file.write_all(&(bytes.len() as u32).to_le_bytes())?;
+            file.write_all(&bytes)?;
+        }
+        self.flush_count += 1;
+        Ok(())
+    }
+}
+
+/// A persistent B-tree mapping sorted `String` keys to opaque byte-blob
+/// values, with node fan-out bounded by `order`.
+#[derive(Debug)]
+pub struct BTreeStore {
+    order: usize,
+    pages: HashMap<u64, Page>,
+    root_id: Option<u64>,
+    next_page_id: u64,
+    writer: PageWriter,
+}
+
+impl BTreeStore {
+    /// Open the page log at `path`, replaying it to rebuild the in-memory
+    /// page table. Starts an empty tree if the log doesn't exist yet.
+    pub fn open(path: &str, order: usize, flush_batch_size: usize) -> Result<Self> {
+        let path = PathBuf::from(path);
+        let (pages, root_id, next_page_id) = Self::replay(&path)?;
+        Ok(Self {
+            order: order.max(2),
+            pages,
+            root_id,
+            next_page_id,
+            writer: PageWriter::new(path, flush_batch_size),
+        })
+    }
+
+    fn empty(path: PathBuf, order: usize, flush_batch_size: usize) -> Self {
+        Self {
+            order: order.max(2),
+            pages: HashMap::new(),
+            root_id: None,
+            next_page_id: 0,
+            writer: PageWriter::new(path, flush_batch_size),
+        }
+    }
+
+    fn replay(path: &Path) -> Result<(HashMap<u64, Page>, Option<u64>, u64)> {
+        let mut pages = HashMap::new();
+        let mut root_id = None;
+        let mut next_page_id = 0u64;
+
+        if !path.exists() {
+            return Ok((pages, root_id, next_page_id));
+        }
+
+        let mut file = File::open(path)?;
+        let mut raw = Vec::new();
+        file.read_to_end(&mut raw)?;
+
+        let mut offset = 0usize;
+        while offset + 4 <= raw.len() {
+            let len = u32::from_le_bytes(raw[offset..offset + 4].try_into().unwrap()) as usize;
+            offset += 4;
+            if offset + len > raw.len() {
+                break;
+            }
+            let record: Record = serde_json::from_slice(&raw[offset..offset + len])?;
+            offset += len;
+
+            match record {
+                Record::Page(page) => {
+                    next_page_id = next_page_id.max(page.id + 1);
+                    pages.insert(page.id, page);
+                }
+                Record::Root(id) => root_id = Some(id),
+                Record::Tombstone(id) => {
+                    pages.remove(&id);
+                }
+            }
+        }
+
+        Ok((pages, root_id, next_page_id))
+    }
+
+    fn allocate_page_id(&mut self) -> u64 {
+        let id = self.next_page_id;
+        self.next_page_id += 1;
+        id
+    }
+
+    fn put_page(&mut self, page: Page) -> Result<()> {
+        self.writer.stage(Record::Page(page.clone()))?;
+        self.pages.insert(page.id, page);
+        Ok(())
+    }
+
+    fn set_root(&mut self, root_id: u64) -> Result<()> {
+        self.writer.stage(Record::Root(root_id))?;
+        self.root_id = Some(root_id);
+        Ok(())
+    }
+
+    fn get_page(&self, id: u64) -> Result<&Page> {
+        self.pages.get(&id).ok_or_else(|| StorageError::Index(format!("missing b-tree page {id}")))
+    }
+
+    /// Force every staged page record to disk, regardless of batch size.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+
+    /// How many times this store has flushed staged records to disk so far,
+    /// whether triggered by `flush_batch_size` filling up or by an explicit
+    /// `flush` call. Callers that must keep sidecar state durably in step
+    /// with the B-tree (rather than only on their own explicit flush) can
+    /// compare this against the value they last saw.
+    pub(crate) fn flush_count(&self) -> u64 {
+        self.writer.flush_count
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let Some(mut current_id) = self.root_id else {
+            return Ok(None);
+        };
+
+        loop {
+            match &self.get_page(current_id)?.node {
+                Node::Leaf { keys, values } => {
+                    return Ok(keys.iter().position(|k| k == key).map(|pos| values[pos].clone()));
+                }
+                Node::Internal { separators, children } => {
+                    let idx = separators.partition_point(|s| s.as_str() <= key);
+                    current_id = children[idx];
+                }
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: String, value: Vec<u8>) -> Result<()> {
+        match self.root_id {
+            None => {
+                let root_id = self.allocate_page_id();
+                self.put_page(Page { id: root_id, node: Node::Leaf { keys: vec![key], values: vec![value] } })?;
+                self.set_root(root_id)?;
+            }
+            Some(root_id) => {
+                if let Some((separator, sibling_id)) = self.insert_into(root_id, key, value)? {
+                    let new_root_id = self.allocate_page_id();
+                    self.put_page(Page {
+                        id: new_root_id,
+                        node: Node::Internal { separators: vec![separator], children: vec![root_id, sibling_id] },
+                    })?;
+                    self.set_root(new_root_id)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert `key`/`value` into the subtree rooted at `page_id`, splitting
+    /// and returning `Some((separator, new_sibling_id))` when the page
+    /// overflows `self.order`.
+    fn insert_into(&mut self, page_id: u64, key: String, value: Vec<u8>) -> Result<Option<(String, u64)>> {
+        let mut page = self.get_page(page_id)?.clone();
+
+        let split = match &mut page.node {
+            Node::Leaf { keys, values } => {
+                match keys.binary_search(&key) {
+                    Ok(pos) => values[pos] = value,
+                    Err(pos) => {
+                        keys.insert(pos, key);
+                        values.insert(pos, value);
+                    }
+                }
+
+                if keys.len() > self.order {
+                    let mid = keys.len() / 2;
+                    let sibling_keys = keys.split_off(mid);
+                    let sibling_values = values.split_off(mid);
+                    let sibling_id = self.allocate_page_id();
+                    let separator = sibling_keys[0].clone();
+                    self.put_page(Page { id: sibling_id, node: Node::Leaf { keys: sibling_keys, values: sibling_values } })?;
+                    Some((separator, sibling_id))
+                } else {
+                    None
+                }
+            }
+            Node::Internal { separators, children } => {
+                let idx = separators.partition_point(|s| s.as_str() <= key.as_str());
+                let child_id = children[idx];
+
+                if let Some((child_separator, new_child_id)) = self.insert_into(child_id, key, value)? {
+                    separators.insert(idx, child_separator);
+                    children.insert(idx + 1, new_child_id);
+
+                    if children.len() > self.order {
+                        let mid = separators.len() / 2;
+                        let up_separator = separators.remove(mid);
+                        let sibling_separators = separators.split_off(mid);
+                        let sibling_children = children.split_off(mid + 1);
+                        let sibling_id = self.allocate_page_id();
+                        self.put_page(Page {
+                            id: sibling_id,
+                            node: Node::Internal { separators: sibling_separators, children: sibling_children },
+                        })?;
+                        Some((up_separator, sibling_id))
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            }
+        };
+
+        self.put_page(page)?;
+        Ok(split)
+    }
+
+    /// Remove `key` from its leaf. Does not merge or rebalance underfull
+    /// siblings (see module docs).
+    pub fn remove(&mut self, key: &str) -> Result<bool> {
+        let Some(mut current_id) = self.root_id else {
+            return Ok(false);
+        };
+
+        loop {
+            let page = self.get_page(current_id)?.clone();
+            match page.node {
+                Node::Leaf { mut keys, mut values } => {
+                    return match keys.iter().position(|k| k == key) {
+                        Some(pos) => {
+                            keys.remove(pos);
+                            values.remove(pos);
+                            self.put_page(Page { id: current_id, node: Node::Leaf { keys, values } })?;
+                            Ok(true)
+                        }
+                        None => Ok(false),
+                    };
+                }
+                Node::Internal { separators, children } => {
+                    let idx = separators.partition_point(|s| s.as_str() <= key);
+                    current_id = children[idx];
+                }
+            }
+        }
+    }
+
+    /// Walk leaf entries in key order, starting at the first key satisfying
+    /// `start` and stopping once `end` is passed. Decodes one leaf page at a
+    /// time rather than materializing the whole range up front.
+    pub fn range(&self, start: Bound<&str>, end: Bound<&str>) -> Result<RangeIter<'_>> {
+        let end = match end {
+            Bound::Included(s) => Bound::Included(s.to_string()),
+            Bound::Excluded(s) => Bound::Excluded(s.to_string()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+
+        let Some(root_id) = self.root_id else {
+            return Ok(RangeIter { store: self, stack: Vec::new(), leaf: None, end, done: true });
+        };
+
+        let mut stack = Vec::new();
+        let mut current_id = root_id;
+        loop {
+            match &self.get_page(current_id)?.node {
+                Node::Internal { separators, children } => {
+                    let idx = match start {
+                        Bound::Included(s) | Bound::Excluded(s) => separators.partition_point(|sep| sep.as_str() <= s),
+                        Bound::Unbounded => 0,
+                    };
+                    stack.push((current_id, idx + 1));
+                    current_id = children[idx];
+                }
+                Node::Leaf { keys, values } => {
+                    let pos = match start {
+                        Bound::Included(s) => keys.partition_point(|k| k.as_str() < s),
+                        Bound::Excluded(s) => keys.partition_point(|k| k.as_str() <= s),
+                        Bound::Unbounded => 0,
+                    };
+                    let leaf = LeafCursor { keys: keys.clone(), values: values.clone(), pos };
+                    return Ok(RangeIter { store: self, stack, leaf: Some(leaf), end, done: false });
+                }
+            }
+        }
+    }
+
+    /// Bulk-load a fresh tree from `pairs` (last value wins on duplicate
+    /// keys), built bottom-up: sort, pack leaves to `order` keys each, then
+    /// repeatedly build a layer of internal nodes above it — using each
+    /// child's first key as the separator — until a single root remains.
+    pub fn bulk_load(path: &str, order: usize, flush_batch_size: usize, mut pairs: Vec<(String, Vec<u8>)>) -> Result<Self> {
+        let order = order.max(2);
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut deduped: Vec<(String, Vec<u8>)> = Vec::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            match deduped.last_mut() {
+                Some(last) if last.0 == key => last.1 = value,
+                _ => deduped.push((key, value)),
+            }
+        }
+
+        let path = PathBuf::from(path);
+        let _ = fs::remove_file(&path);
+        let mut store = Self::empty(path, order, flush_batch_size);
+
+        if deduped.is_empty() {
+            store.flush()?;
+            return Ok(store);
+        }
+
+        let mut layer_ids = Vec::new();
+        let mut layer_first_keys = Vec::new();
+        for chunk in deduped.chunks(order) {
+            let id = store.allocate_page_id();
+            let keys: Vec<String> = chunk.iter().map(|(k, _)| k.clone()).collect();
+            let values: Vec<Vec<u8>> = chunk.iter().map(|(_, v)| v.clone()).collect();
+            layer_first_keys.push(keys[0].clone());
+            store.put_page(Page { id, node: Node::Leaf { keys, values } })?;
+            layer_ids.push(id);
+        }
+
+        while layer_ids.len() > 1 {
+            let mut next_ids = Vec::new();
+            let mut next_first_keys = Vec::new();
+            for (child_ids, child_keys) in layer_ids.chunks(order).zip(layer_first_keys.chunks(order)) {
+                let id = store.allocate_page_id();
+                let separators = child_keys[1..].to_vec();
+                let children = child_ids.to_vec();
+                next_first_keys.push(child_keys[0].clone());
+                store.put_page(Page { id, node: Node::Internal { separators, children } })?;
+                next_ids.push(id);
+            }
+            layer_ids = next_ids;
+            layer_first_keys = next_first_keys;
+        }
+
+        store.set_root(layer_ids[0])?;
+        store.flush()?;
+        Ok(store)
+    }
+}
+
+struct LeafCursor {
+    keys: Vec<String>,
+    values: Vec<Vec<u8>>,
+    pos: usize,
+}
+
+/// Lazy, ordered walk over `(key, value)` pairs produced by `BTreeStore::range`.
+///
+/// Holds a stack of `(page_id, next_child_index)` frames marking the way
+/// back up from the current leaf to the root, so advancing past the end of
+/// a leaf finds the next one by popping to the nearest ancestor with an
+/// unvisited child and descending leftmost from there — no sibling pointers
+/// needed on the leaves themselves.
+pub struct RangeIter<'a> {
+    store: &'a BTreeStore,
+    stack: Vec<(u64, usize)>,
+    leaf: Option<LeafCursor>,
+    end: Bound<String>,
+    done: bool,
+}
+
+impl<'a> RangeIter<'a> {
+    fn advance_to_next_leaf(&mut self) -> bool {
+        while let Some((page_id, next_idx)) = self.stack.pop() {
+            let Ok(page) = self.store.get_page(page_id) else { continue };
+            let Node::Internal { children, .. } = &page.node else { continue };
+            if next_idx >= children.len() {
+                continue;
+            }
+
+            self.stack.push((page_id, next_idx + 1));
+            let mut current_id = children[next_idx];
+            loop {
+                let Ok(page) = self.store.get_page(current_id) else { return false };
+                match &page.node {
+                    Node::Internal { children, .. } => {
+                        self.stack.push((current_id, 1));
+                        current_id = children[0];
+                    }
+                    Node::Leaf { keys, values } => {
+                        self.leaf = Some(LeafCursor { keys: keys.clone(), values: values.clone(), pos: 0 });
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+impl<'a> Iterator for RangeIter<'a> {
+    type Item = (String, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return None;
+            }
+
+            let Some(leaf) = &mut self.leaf else {
+                self.done = true;
+                return None;
+            };
+
+            if leaf.pos >= leaf.keys.len() {
+                if !self.advance_to_next_leaf() {
+                    self.done = true;
+                }
+                continue;
+            }
+
+            let key = leaf.keys[leaf.pos].clone();
+            let past_end = match &self.end {
+                Bound::Included(e) => key.as_str() > e.as_str(),
+                Bound::Excluded(e) => key.as_str() >= e.as_str(),
+                Bound::Unbounded => false,
+            };
+            if past_end {
+                self.done = true;
+                return None;
+            }
+
+            let value = leaf.values[leaf.pos].clone();
+            leaf.pos += 1;
+            return Some((key, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn temp_btree_path(name: &str) -> String {
+        env::temp_dir()
+            .join(format!("neodb_btree_test_{name}_{}.btree", std::process::id()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn test_insert_and_get_round_trip() {
+        let path = temp_btree_path("roundtrip");
+        let _ = fs::remove_file(&path);
+
+        let mut store = BTreeStore::open(&path, 4, 1).unwrap();
+        store.insert("b".to_string(), b"r2".to_vec()).unwrap();
+        store.insert("a".to_string(), b"r1".to_vec()).unwrap();
+
+        assert_eq!(store.get("a").unwrap(), Some(b"r1".to_vec()));
+        assert_eq!(store.get("b").unwrap(), Some(b"r2".to_vec()));
+        assert_eq!(store.get("missing").unwrap(), None);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_splits_propagate_and_keep_every_key_reachable() {
+        let path = temp_btree_path("splits");
+        let _ = fs::remove_file(&path);
+
+        let mut store = BTreeStore::open(&path, 3, 1).unwrap();
+        for i in 0..50 {
+            store.insert(format!("k{i:03}"), format!("r{i}").into_bytes()).unwrap();
+        }
+
+        for i in 0..50 {
+            assert_eq!(store.get(&format!("k{i:03}")).unwrap(), Some(format!("r{i}").into_bytes()));
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_remove_drops_the_key_but_not_its_siblings() {
+        let path = temp_btree_path("remove");
+        let _ = fs::remove_file(&path);
+
+        let mut store = BTreeStore::open(&path, 4, 1).unwrap();
+        store.insert("a".to_string(), b"r1".to_vec()).unwrap();
+        store.insert("b".to_string(), b"r2".to_vec()).unwrap();
+
+        assert!(store.remove("a").unwrap());
+        assert!(!store.remove("a").unwrap());
+        assert_eq!(store.get("a").unwrap(), None);
+        assert_eq!(store.get("b").unwrap(), Some(b"r2".to_vec()));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_tree_survives_reopen_after_flush() {
+        let path = temp_btree_path("reopen");
+        let _ = fs::remove_file(&path);
+
+        {
+            let mut store = BTreeStore::open(&path, 3, 8).unwrap();
+            for i in 0..10 {
+                store.insert(format!("k{i}"), format!("r{i}").into_bytes()).unwrap();
+            }
+            store.flush().unwrap();
+        }
+
+        let reopened = BTreeStore::open(&path, 3, 8).unwrap();
+        for i in 0..10 {
+            assert_eq!(reopened.get(&format!("k{i}")).unwrap(), Some(format!("r{i}").into_bytes()));
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_bulk_load_builds_a_tree_with_every_pair_reachable() {
+        let path = temp_btree_path("bulk");
+        let _ = fs::remove_file(&path);
+
+        let pairs: Vec<(String, Vec<u8>)> = (0..100)
+            .map(|i| (format!("k{i:03}"), format!("r{i}").into_bytes()))
+            .collect();
+
+        let store = BTreeStore::bulk_load(&path, 4, 8, pairs).unwrap();
+        for i in 0..100 {
+            assert_eq!(store.get(&format!("k{i:03}")).unwrap(), Some(format!("r{i}").into_bytes()));
+        }
+
+        let reopened = BTreeStore::open(&path, 4, 8).unwrap();
+        assert_eq!(reopened.get("k050").unwrap(), Some(b"r50".to_vec()));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_bulk_load_last_value_wins_on_duplicate_keys() {
+        let path = temp_btree_path("bulk_dedup");
+        let _ = fs::remove_file(&path);
+
+        let pairs = vec![
+            ("a".to_string(), b"first".to_vec()),
+            ("a".to_string(), b"second".to_vec()),
+        ];
+        let store = BTreeStore::bulk_load(&path, 4, 8, pairs).unwrap();
+        assert_eq!(store.get("a").unwrap(), Some(b"second".to_vec()));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_range_walks_keys_in_order_across_many_leaf_splits() {
+        let path = temp_btree_path("range");
+        let _ = fs::remove_file(&path);
+
+        let mut store = BTreeStore::open(&path, 3, 1).unwrap();
+        for i in 0..50 {
+            store.insert(format!("k{i:03}"), format!("r{i}").into_bytes()).unwrap();
+        }
+
+        let collected: Vec<String> = store
+            .range(Bound::Included("k010"), Bound::Excluded("k015"))
+            .unwrap()
+            .map(|(k, _)| k)
+            .collect();
+        let expected: Vec<String> = (10..15).map(|i| format!("k{i:03}")).collect();
+        assert_eq!(collected, expected);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_range_unbounded_visits_every_key_in_order() {
+        let path = temp_btree_path("range_unbounded");
+        let _ = fs::remove_file(&path);
+
+        let mut store = BTreeStore::open(&path, 3, 1).unwrap();
+        for i in (0..30).rev() {
+            store.insert(format!("k{i:03}"), format!("r{i}").into_bytes()).unwrap();
+        }
+
+        let collected: Vec<String> = store.range(Bound::Unbounded, Bound::Unbounded).unwrap().map(|(k, _)| k).collect();
+        let expected: Vec<String> = (0..30).map(|i| format!("k{i:03}")).collect();
+        assert_eq!(collected, expected);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
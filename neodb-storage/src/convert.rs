@@ -0,0 +1,129 @@
+//! Offline backend-to-backend data migration
+//!
+//! `convert_backend` is the analogue of a `convert_db` tool: it opens a
+//! source `StorageEngine`, streams every key/value through a prefix
+//! iterator, and re-`put`s each one into a freshly opened destination
+//! engine — typically of a different `BackendKind` (e.g. moving a dataset
+//! off `Embedded` and into `RocksDb`/`Lmdb`/`Sqlite`). Puts are idempotent,
+//! so re-running a partially completed conversion is safe.
+
+use crate::engine::{StorageConfig, StorageEngine};
+use crate::{Result, StorageError};
+
+/// Outcome of a completed backend conversion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionReport {
+    /// Number of key/value pairs copied from source to destination.
+    pub keys_copied: u64,
+    /// `StorageStats::total_keys` reported by the source engine afterward.
+    pub source_total_keys: u64,
+    /// `StorageStats::total_keys` reported by the destination engine afterward.
+    pub destination_total_keys: u64,
+}
+
+/// Copy every key/value pair from the engine described by `src` into a
+/// freshly opened engine described by `dst`, refusing to run if the two
+/// configs point at the same path. Verifies the source and destination key
+/// counts match once the copy completes.
+pub async fn convert_backend(src: &StorageConfig, dst: &StorageConfig) -> Result<ConversionReport> {
+    if src.path == dst.path {
+        return Err(StorageError::IdenticalStoragePaths(src.path.clone()));
+    }
+
+    let mut source = StorageEngine::with_config(src.clone());
+    source.open().await?;
+
+    let mut destination = StorageEngine::with_config(dst.clone());
+    destination.open().await?;
+
+    let mut keys_copied: u64 = 0;
+    for (key, value) in source.iter_prefix("").await? {
+        destination.put(&key, &value).await?;
+        keys_copied += 1;
+        if keys_copied.is_multiple_of(1000) {
+            println!("convert_backend: copied {keys_copied} keys so far...");
+        }
+    }
+
+    let source_total_keys = source.stats()?.total_keys;
+    let destination_total_keys = destination.stats()?.total_keys;
+
+    source.close().await?;
+    destination.close().await?;
+
+    if source_total_keys != destination_total_keys {
+        return Err(StorageError::ConversionMismatch {
+            source_keys: source_total_keys,
+            destination_keys: destination_total_keys,
+        });
+    }
+
+    println!("convert_backend: copied {keys_copied} keys, {source_total_keys} verified on both sides");
+
+    Ok(ConversionReport {
+        keys_copied,
+        source_total_keys,
+        destination_total_keys,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::BackendKind;
+    use std::env;
+    use std::fs;
+
+    fn test_config(name: &str, backend: BackendKind) -> StorageConfig {
+        let path = env::temp_dir()
+            .join(format!("neodb_convert_test_{name}_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let _ = fs::remove_file(format!("{path}.wal"));
+        StorageConfig { path, backend, ..StorageConfig::default() }
+    }
+
+    #[tokio::test]
+    async fn test_convert_backend_copies_all_keys_to_a_different_backend() {
+        let src = test_config("convert_src", BackendKind::Embedded);
+        let dst = test_config("convert_dst", BackendKind::Sqlite);
+
+        let mut source = StorageEngine::with_config(src.clone());
+        source.open().await.unwrap();
+        source.put("n:1", b"alice").await.unwrap();
+        source.put("n:2", b"bob").await.unwrap();
+        source.close().await.unwrap();
+
+        let report = convert_backend(&src, &dst).await.unwrap();
+        assert_eq!(report.keys_copied, 2);
+        assert_eq!(report.source_total_keys, report.destination_total_keys);
+
+        let mut destination = StorageEngine::with_config(dst);
+        destination.open().await.unwrap();
+        assert_eq!(destination.get("n:1").await.unwrap(), Some(b"alice".to_vec()));
+        assert_eq!(destination.get("n:2").await.unwrap(), Some(b"bob".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_convert_backend_refuses_identical_paths() {
+        let config = test_config("convert_same", BackendKind::Embedded);
+        let result = convert_backend(&config, &config).await;
+        assert!(matches!(result, Err(StorageError::IdenticalStoragePaths(_))));
+    }
+
+    #[tokio::test]
+    async fn test_convert_backend_is_idempotent_on_rerun() {
+        let src = test_config("convert_rerun_src", BackendKind::Embedded);
+        let dst = test_config("convert_rerun_dst", BackendKind::RocksDb);
+
+        let mut source = StorageEngine::with_config(src.clone());
+        source.open().await.unwrap();
+        source.put("n:1", b"alice").await.unwrap();
+        source.close().await.unwrap();
+
+        convert_backend(&src, &dst).await.unwrap();
+        let second_report = convert_backend(&src, &dst).await.unwrap();
+        assert_eq!(second_report.keys_copied, 1);
+        assert_eq!(second_report.destination_total_keys, 1);
+    }
+}
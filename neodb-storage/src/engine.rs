@@ -3,8 +3,12 @@
 //! Provides the core storage abstraction for NeoDB with support for
 //! persistent storage backends like RocksDB.
 
-use std::path::Path;
+use std::path::PathBuf;
+use std::time::Instant;
 use serde::{Serialize, Deserialize};
+use crate::backend::{BackendKind, OpenBackend, StorageBackend, Transaction};
+use crate::metrics::StorageMetrics;
+use crate::persistence::{PersistenceConfig, PersistenceManager, WalEntry};
 use crate::{Result, StorageError};
 
 /// Configuration for the storage engine
@@ -14,6 +18,7 @@ pub struct StorageConfig {
     pub cache_size: usize,
     pub compression_enabled: bool,
     pub sync_writes: bool,
+    pub backend: BackendKind,
 }
 
 impl Default for StorageConfig {
@@ -23,19 +28,22 @@ impl Default for StorageConfig {
             cache_size: 128 * 1024 * 1024, // 128MB
             compression_enabled: true,
             sync_writes: false,
+            backend: BackendKind::default(),
         }
     }
 }
 
 /// Main storage engine interface
 ///
-/// This will be implemented using RocksDB for high-performance
-/// persistent storage with ACID guarantees.
+/// Delegates durable reads/writes to a pluggable `StorageBackend` (selected
+/// via `StorageConfig::backend`) and logs every mutation to a write-ahead
+/// log before applying it, replaying any uncommitted entries on `open`.
 #[derive(Debug)]
 pub struct StorageEngine {
     config: StorageConfig,
-    // TODO: Add RocksDB instance
-    // db: Option<rocksdb::DB>,
+    persistence: PersistenceManager,
+    backend: Option<OpenBackend>,
+    metrics: StorageMetrics,
 }
 
 impl StorageEngine {
@@ -48,81 +56,158 @@ impl StorageEngine {
     pub fn with_config(config: StorageConfig) -> Self {
         Self {
             config,
+            persistence: PersistenceManager::new(PersistenceConfig::default()),
+            backend: None,
+            metrics: StorageMetrics::default(),
         }
     }
 
-    /// Initialize/open the storage engine
+    fn backend_name(&self) -> &'static str {
+        match self.config.backend {
+            BackendKind::Embedded => "embedded",
+            BackendKind::Sqlite => "sqlite",
+            BackendKind::RocksDb => "rocksdb",
+            BackendKind::Lmdb => "lmdb",
+        }
+    }
+
+    fn wal_path(&self) -> PathBuf {
+        PathBuf::from(format!("{}.wal", self.config.path))
+    }
+
+    fn backend_mut(&mut self) -> Result<&mut OpenBackend> {
+        self.backend.as_mut().ok_or(StorageError::NotInitialized)
+    }
+
+    fn backend_ref(&self) -> Result<&OpenBackend> {
+        self.backend.as_ref().ok_or(StorageError::NotInitialized)
+    }
+
+    /// Initialize/open the storage engine, replaying any WAL entries left
+    /// behind by an unclean shutdown.
     pub async fn open(&mut self) -> Result<()> {
-        // TODO: Initialize RocksDB
-        // let db = rocksdb::DB::open_default(&self.config.path)
-        //     .map_err(|e| StorageError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
-        // self.db = Some(db);
-        
+        let mut backend = OpenBackend::open(&self.config.backend, &self.config.path);
+        for entry in self.persistence.replay_wal(&self.wal_path()).await? {
+            match entry {
+                WalEntry::Put { key, value } => backend.put(key, value)?,
+                WalEntry::Delete { key } => backend.delete(&key)?,
+            }
+        }
+        self.backend = Some(backend);
+
         println!("Storage engine opened at: {}", self.config.path);
         Ok(())
     }
 
-    /// Store a key-value pair
-    pub async fn put(&self, key: &str, value: &[u8]) -> Result<()> {
-        // TODO: Implement with RocksDB
-        // if let Some(db) = &self.db {
-        //     db.put(key, value)
-        //         .map_err(|e| StorageError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
-        // }
-        
+    /// Store a key-value pair, write-ahead logging it before it is applied.
+    pub async fn put(&mut self, key: &str, value: &[u8]) -> Result<()> {
+        let started = Instant::now();
+        let entry = WalEntry::Put { key: key.to_string(), value: value.to_vec() };
+        self.persistence.append_wal_entry(&self.wal_path(), &entry).await?;
+        self.backend_mut()?.put(key.to_string(), value.to_vec())?;
+        self.metrics.record_put(value.len(), started.elapsed());
+
         println!("PUT: {} -> {} bytes", key, value.len());
         Ok(())
     }
 
     /// Retrieve a value by key
     pub async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
-        // TODO: Implement with RocksDB
-        // if let Some(db) = &self.db {
-        //     return db.get(key)
-        //         .map_err(|e| StorageError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)));
-        // }
-        
+        let started = Instant::now();
         println!("GET: {}", key);
-        Ok(None)
+        let value = self.backend_ref()?.get(key)?;
+        self.metrics.record_get(value.as_ref().map(Vec::len).unwrap_or(0), started.elapsed());
+        Ok(value)
     }
 
-    /// Delete a key-value pair
-    pub async fn delete(&self, key: &str) -> Result<()> {
-        // TODO: Implement with RocksDB
-        // if let Some(db) = &self.db {
-        //     db.delete(key)
-        //         .map_err(|e| StorageError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
-        // }
-        
+    /// Delete a key-value pair, write-ahead logging it before it is applied.
+    pub async fn delete(&mut self, key: &str) -> Result<()> {
+        let started = Instant::now();
+        let entry = WalEntry::Delete { key: key.to_string() };
+        self.persistence.append_wal_entry(&self.wal_path(), &entry).await?;
+        self.backend_mut()?.delete(key)?;
+        self.metrics.record_delete(started.elapsed());
+
         println!("DELETE: {}", key);
         Ok(())
     }
 
     /// Check if a key exists
     pub async fn exists(&self, key: &str) -> Result<bool> {
-        // TODO: Implement with RocksDB
         println!("EXISTS: {}", key);
-        Ok(false)
+        Ok(self.backend_ref()?.get(key)?.is_some())
+    }
+
+    /// List all key-value pairs whose key starts with `prefix`.
+    pub async fn iter_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        self.backend_ref()?.iter_prefix(prefix)
+    }
+
+    /// Apply a batch of writes atomically: every staged `put`/`delete` is
+    /// write-ahead logged and applied only if `f` returns `Ok`.
+    pub async fn transaction<F>(&mut self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut Transaction) -> Result<()>,
+    {
+        let mut tx = Transaction::default();
+        f(&mut tx)?;
+
+        let wal_path = self.wal_path();
+        for key in tx.deletes() {
+            self.persistence
+                .append_wal_entry(&wal_path, &WalEntry::Delete { key: key.clone() })
+                .await?;
+        }
+        for (key, value) in tx.puts() {
+            self.persistence
+                .append_wal_entry(&wal_path, &WalEntry::Put { key: key.clone(), value: value.clone() })
+                .await?;
+        }
+
+        let backend = self.backend_mut()?;
+        for key in tx.deletes() {
+            backend.delete(key)?;
+        }
+        for (key, value) in tx.puts() {
+            backend.put(key.clone(), value.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Flush the WAL: it has already been applied to the backend as each
+    /// entry was written, so a checkpoint simply truncates the log.
+    pub async fn checkpoint(&self) -> Result<()> {
+        self.persistence.checkpoint(&self.wal_path()).await
     }
 
     /// Close the storage engine
     pub async fn close(&mut self) -> Result<()> {
-        // TODO: Close RocksDB
-        // self.db = None;
-        
+        self.backend = None;
+
         println!("Storage engine closed");
         Ok(())
     }
 
-    /// Get storage statistics
+    /// Get storage statistics, fed from the same counters that back
+    /// `metrics_text` so the two never disagree.
     pub fn stats(&self) -> Result<StorageStats> {
-        // TODO: Get actual stats from RocksDB
+        let total_keys = self
+            .backend_ref()
+            .map(|b| b.iter_prefix("").map(|entries| entries.len()).unwrap_or(0))
+            .unwrap_or(0) as u64;
+
         Ok(StorageStats {
-            total_keys: 0,
-            total_size_bytes: 0,
+            total_keys,
+            total_size_bytes: self.metrics.bytes_written(),
             cache_hit_rate: 0.0,
         })
     }
+
+    /// Render this engine's counters, gauges, and latency histograms in
+    /// Prometheus text exposition format, labeled with the active backend.
+    pub fn metrics_text(&self) -> String {
+        self.metrics.render_prometheus_text(self.backend_name(), 0.0)
+    }
 }
 
 impl Default for StorageEngine {
@@ -142,6 +227,17 @@ pub struct StorageStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::env;
+    use std::fs;
+
+    fn test_config(name: &str) -> StorageConfig {
+        let path = env::temp_dir()
+            .join(format!("neodb_engine_test_{name}_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let _ = fs::remove_file(format!("{path}.wal"));
+        StorageConfig { path, ..StorageConfig::default() }
+    }
 
     #[tokio::test]
     async fn test_storage_engine_creation() {
@@ -152,19 +248,72 @@ mod tests {
 
     #[tokio::test]
     async fn test_storage_operations() {
-        let mut engine = StorageEngine::new();
+        let mut engine = StorageEngine::with_config(test_config("ops"));
         engine.open().await.unwrap();
 
-        let key = "test_key";
+        let key = "n:test_key";
         let value = b"test_value";
 
         assert!(engine.put(key, value).await.is_ok());
-        
-        // Note: This will return None in the placeholder implementation
-        let result = engine.get(key).await.unwrap();
-        // assert_eq!(result, Some(value.to_vec()));
+        assert_eq!(engine.get(key).await.unwrap(), Some(value.to_vec()));
+        assert!(engine.exists(key).await.unwrap());
 
         assert!(engine.delete(key).await.is_ok());
+        assert_eq!(engine.get(key).await.unwrap(), None);
         assert!(engine.close().await.is_ok());
+
+        engine.checkpoint().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wal_is_replayed_after_reopen() {
+        let config = test_config("replay");
+
+        let mut engine = StorageEngine::with_config(config.clone());
+        engine.open().await.unwrap();
+        engine.put("n:1", b"alice").await.unwrap();
+        // Simulate a crash: drop the engine without checkpointing.
+        drop(engine);
+
+        let mut reopened = StorageEngine::with_config(config);
+        reopened.open().await.unwrap();
+        assert_eq!(reopened.get("n:1").await.unwrap(), Some(b"alice".to_vec()));
+
+        reopened.checkpoint().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_transaction_applies_all_writes_atomically() {
+        let mut engine = StorageEngine::with_config(test_config("txn"));
+        engine.open().await.unwrap();
+
+        engine
+            .transaction(|tx| {
+                tx.put("n:1", b"alice".to_vec());
+                tx.put("n:2", b"bob".to_vec());
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(engine.get("n:1").await.unwrap(), Some(b"alice".to_vec()));
+        assert_eq!(engine.get("n:2").await.unwrap(), Some(b"bob".to_vec()));
+
+        engine.checkpoint().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_metrics_text_and_stats_agree_on_bytes_written() {
+        let mut engine = StorageEngine::with_config(test_config("metrics"));
+        engine.open().await.unwrap();
+        engine.put("n:1", b"alice").await.unwrap();
+
+        let text = engine.metrics_text();
+        assert!(text.contains("neodb_storage_ops_total{operation=\"put\",backend=\"embedded\"} 1"));
+
+        let stats = engine.stats().unwrap();
+        assert_eq!(stats.total_size_bytes, 5);
+
+        engine.checkpoint().await.unwrap();
     }
 }
\ No newline at end of file
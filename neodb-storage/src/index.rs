@@ -1,7 +1,14 @@
 //! Indexing system for NeoDB storage engine
 
 use std::collections::HashMap;
-use serde::{Serialize, Deserialize};
+use std::fs;
+use std::ops::Bound;
+
+use futures::{stream, Stream, StreamExt};
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use crate::btree::BTreeStore;
 use crate::{Result, StorageError};
 
 /// Index configuration
@@ -9,53 +16,417 @@ use crate::{Result, StorageError};
 pub struct IndexConfig {
     pub name: String,
     pub unique: bool,
+    /// Fan-out of the backing B-tree's internal and leaf nodes.
     pub btree_order: usize,
+    /// Where the B-tree's page log lives on disk.
+    pub path: String,
+    /// Number of staged page records the B-tree accumulates before
+    /// flushing them to disk in one sequential pass.
+    pub flush_batch_size: usize,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            name: "index".to_string(),
+            unique: false,
+            btree_order: 32,
+            path: "./neodb_index".to_string(),
+            flush_batch_size: 64,
+        }
+    }
 }
 
-/// Simple in-memory index implementation
-/// TODO: Replace with persistent B-tree or LSM-tree based index
+/// Bidirectional mapping between record-id strings and the dense `u32`
+/// handles that roaring bitmaps store. Persisted as a sidecar file next to
+/// the B-tree's own page log, saved whenever `BTreeStore` flushes its pages
+/// (an explicit flush or a full batch) rather than on every mutation.
+#[derive(Debug, Default)]
+struct Interner {
+    ids: HashMap<String, u32>,
+    strings: Vec<String>,
+}
+
+impl Interner {
+    fn sidecar_path(index_path: &str) -> String {
+        format!("{index_path}.interner")
+    }
+
+    fn load(index_path: &str) -> Result<Self> {
+        let path = Self::sidecar_path(index_path);
+        if !std::path::Path::new(&path).exists() {
+            return Ok(Self::default());
+        }
+
+        let raw = fs::read(&path)?;
+        let strings: Vec<String> = serde_json::from_slice(&raw)?;
+        let ids = strings.iter().enumerate().map(|(id, s)| (s.clone(), id as u32)).collect();
+        Ok(Self { ids, strings })
+    }
+
+    fn save(&self, index_path: &str) -> Result<()> {
+        let bytes = serde_json::to_vec(&self.strings)?;
+        fs::write(Self::sidecar_path(index_path), bytes)?;
+        Ok(())
+    }
+
+    /// Look up `record_id`'s handle, assigning a new one if it hasn't been
+    /// seen before.
+    fn intern(&mut self, record_id: &str) -> u32 {
+        if let Some(&id) = self.ids.get(record_id) {
+            return id;
+        }
+
+        let id = self.strings.len() as u32;
+        self.strings.push(record_id.to_string());
+        self.ids.insert(record_id.to_string(), id);
+        id
+    }
+
+    /// Look up `record_id`'s handle without assigning one.
+    fn lookup(&self, record_id: &str) -> Option<u32> {
+        self.ids.get(record_id).copied()
+    }
+
+    fn resolve(&self, id: u32) -> Option<&str> {
+        self.strings.get(id as usize).map(String::as_str)
+    }
+}
+
+/// Index backed by a persistent B-tree (see `btree`), mapping keys to
+/// compressed roaring bitmaps of interned record IDs, with fan-out
+/// `IndexConfig::btree_order`.
 #[derive(Debug)]
 pub struct Index {
     config: IndexConfig,
-    // TODO: Replace HashMap with persistent index structure
-    data: HashMap<String, Vec<String>>, // key -> list of record IDs
+    tree: BTreeStore,
+    interner: Interner,
+    /// `tree.flush_count()` as of the last time we saved the interner
+    /// sidecar, so we can tell when `BTreeStore`'s own `flush_batch_size`
+    /// has flushed pages to disk behind our back and the sidecar needs
+    /// saving to stay in step — not just on our own explicit `flush`.
+    last_persisted_flush_count: u64,
 }
 
 impl Index {
-    pub fn new(config: IndexConfig) -> Self {
-        Self {
-            config,
-            data: HashMap::new(),
+    pub fn new(config: IndexConfig) -> Result<Self> {
+        let tree = BTreeStore::open(&config.path, config.btree_order, config.flush_batch_size)?;
+        let interner = Interner::load(&config.path)?;
+        let last_persisted_flush_count = tree.flush_count();
+        Ok(Self { config, tree, interner, last_persisted_flush_count })
+    }
+
+    /// Save the interner sidecar if the B-tree has auto-flushed pages to
+    /// disk since we last persisted it, so a crash right after such an
+    /// auto-flush can't leave durable bitmaps referencing ids the sidecar
+    /// doesn't know about yet.
+    fn sync_interner_with_tree_flushes(&mut self) -> Result<()> {
+        let flush_count = self.tree.flush_count();
+        if flush_count != self.last_persisted_flush_count {
+            self.interner.save(&self.config.path)?;
+            self.last_persisted_flush_count = flush_count;
+        }
+        Ok(())
+    }
+
+    /// Bulk-load `pairs` into a fresh index, replacing whatever page log
+    /// already exists at `config.path`. Far faster than inserting one at a
+    /// time for an initial load since it builds the tree bottom-up instead
+    /// of splitting pages as it goes.
+    pub fn bulk_load(config: IndexConfig, pairs: Vec<(String, Vec<String>)>) -> Result<Self> {
+        let mut interner = Interner::default();
+        let mut encoded = Vec::with_capacity(pairs.len());
+        for (key, record_ids) in pairs {
+            let mut bitmap = RoaringBitmap::new();
+            for record_id in record_ids {
+                bitmap.insert(interner.intern(&record_id));
+            }
+            encoded.push((key, encode_bitmap(&bitmap)?));
+        }
+
+        let tree = BTreeStore::bulk_load(&config.path, config.btree_order, config.flush_batch_size, encoded)?;
+        interner.save(&config.path)?;
+        let last_persisted_flush_count = tree.flush_count();
+        Ok(Self { config, tree, interner, last_persisted_flush_count })
+    }
+
+    fn load_bitmap(&self, key: &str) -> Result<RoaringBitmap> {
+        match self.tree.get(key)? {
+            Some(bytes) => decode_bitmap(&bytes),
+            None => Ok(RoaringBitmap::new()),
         }
     }
 
+    fn store_bitmap(&mut self, key: String, bitmap: &RoaringBitmap) -> Result<()> {
+        self.tree.insert(key, encode_bitmap(bitmap)?)
+    }
+
+    /// Map a bitmap of interned ids back to their record-id strings.
+    fn decode_ids(&self, bitmap: &RoaringBitmap) -> Vec<String> {
+        bitmap.iter().filter_map(|id| self.interner.resolve(id)).map(str::to_string).collect()
+    }
+
     pub async fn insert(&mut self, key: String, record_id: String) -> Result<()> {
-        if self.config.unique && self.data.contains_key(&key) {
+        let mut bitmap = self.load_bitmap(&key)?;
+        if self.config.unique && !bitmap.is_empty() {
             return Err(StorageError::Index(format!("Duplicate key in unique index: {}", key)));
         }
 
-        self.data
-            .entry(key)
-            .or_insert_with(Vec::new)
-            .push(record_id);
-        
+        bitmap.insert(self.interner.intern(&record_id));
+        self.store_bitmap(key, &bitmap)?;
+        self.sync_interner_with_tree_flushes()?;
         Ok(())
     }
 
     pub async fn get(&self, key: &str) -> Result<Vec<String>> {
-        Ok(self.data.get(key).cloned().unwrap_or_default())
+        Ok(self.decode_ids(&self.load_bitmap(key)?))
     }
 
     pub async fn remove(&mut self, key: &str, record_id: &str) -> Result<bool> {
-        if let Some(records) = self.data.get_mut(key) {
-            if let Some(pos) = records.iter().position(|x| x == record_id) {
-                records.remove(pos);
-                if records.is_empty() {
-                    self.data.remove(key);
-                }
-                return Ok(true);
-            }
+        let Some(id) = self.interner.lookup(record_id) else {
+            return Ok(false);
+        };
+
+        let mut bitmap = self.load_bitmap(key)?;
+        if !bitmap.remove(id) {
+            return Ok(false);
+        }
+
+        if bitmap.is_empty() {
+            self.tree.remove(key)?;
+        } else {
+            self.store_bitmap(key.to_string(), &bitmap)?;
+        }
+        self.sync_interner_with_tree_flushes()?;
+        Ok(true)
+    }
+
+    /// AND the bitmaps of `keys` together and resolve the surviving ids back
+    /// to record-id strings. Empty input yields an empty result.
+    pub fn intersect(&self, keys: &[&str]) -> Result<Vec<String>> {
+        let mut keys = keys.iter();
+        let Some(first) = keys.next() else {
+            return Ok(Vec::new());
+        };
+
+        let mut acc = self.load_bitmap(first)?;
+        for key in keys {
+            acc &= self.load_bitmap(key)?;
+        }
+        Ok(self.decode_ids(&acc))
+    }
+
+    /// OR the bitmaps of `keys` together and resolve the union's ids back to
+    /// record-id strings.
+    pub fn union(&self, keys: &[&str]) -> Result<Vec<String>> {
+        let mut acc = RoaringBitmap::new();
+        for key in keys {
+            acc |= self.load_bitmap(key)?;
+        }
+        Ok(self.decode_ids(&acc))
+    }
+
+    /// Stream `(key, record_ids)` entries whose key falls within `start..end`,
+    /// in ascending key order, decoding one B-tree leaf at a time instead of
+    /// collecting the whole range up front.
+    pub fn range<'a>(&'a self, start: Bound<&str>, end: Bound<&str>) -> Result<impl Stream<Item = (String, Vec<String>)> + 'a> {
+        let iter = self.tree.range(start, end)?;
+        Ok(stream::iter(iter).map(move |(key, bytes)| {
+            let bitmap = decode_bitmap(&bytes).unwrap_or_default();
+            (key, self.decode_ids(&bitmap))
+        }))
+    }
+
+    /// Stream `(key, record_ids)` entries whose key starts with `prefix`, in
+    /// ascending key order.
+    pub fn prefix_scan<'a>(&'a self, prefix: &'a str) -> Result<impl Stream<Item = (String, Vec<String>)> + 'a> {
+        let inner = self.range(Bound::Included(prefix), Bound::Unbounded)?;
+        Ok(inner.take_while(move |(key, _)| futures::future::ready(key.starts_with(prefix))))
+    }
+
+    /// Force every staged B-tree page record, and the interner, to disk.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.tree.flush()?;
+        self.interner.save(&self.config.path)?;
+        self.last_persisted_flush_count = self.tree.flush_count();
+        Ok(())
+    }
+}
+
+fn encode_bitmap(bitmap: &RoaringBitmap) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    bitmap.serialize_into(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn decode_bitmap(bytes: &[u8]) -> Result<RoaringBitmap> {
+    RoaringBitmap::deserialize_from(bytes).map_err(|e| StorageError::Index(format!("corrupt bitmap: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    fn test_config(name: &str) -> IndexConfig {
+        let path = env::temp_dir()
+            .join(format!("neodb_index_test_{name}_{}.btree", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(format!("{path}.interner"));
+        IndexConfig { name: name.to_string(), path, ..IndexConfig::default() }
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_get() {
+        let mut index = Index::new(test_config("insert_get")).unwrap();
+        index.insert("key1".to_string(), "record1".to_string()).await.unwrap();
+        index.insert("key1".to_string(), "record2".to_string()).await.unwrap();
+
+        let mut records = index.get("key1").await.unwrap();
+        records.sort();
+        assert_eq!(records, vec!["record1".to_string(), "record2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_unique_index_rejects_duplicate_keys() {
+        let config = IndexConfig { unique: true, ..test_config("unique") };
+        let mut index = Index::new(config).unwrap();
+
+        index.insert("key1".to_string(), "record1".to_string()).await.unwrap();
+        let result = index.insert("key1".to_string(), "record2".to_string()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_non_unique_index_does_not_silently_duplicate_the_same_record() {
+        let mut index = Index::new(test_config("dedup")).unwrap();
+        index.insert("key1".to_string(), "record1".to_string()).await.unwrap();
+        index.insert("key1".to_string(), "record1".to_string()).await.unwrap();
+
+        assert_eq!(index.get("key1").await.unwrap(), vec!["record1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_remove_record() {
+        let mut index = Index::new(test_config("remove")).unwrap();
+        index.insert("key1".to_string(), "record1".to_string()).await.unwrap();
+
+        assert!(index.remove("key1", "record1").await.unwrap());
+        assert!(!index.remove("key1", "record1").await.unwrap());
+        assert_eq!(index.get("key1").await.unwrap(), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn test_index_survives_reopen_after_flush() {
+        let config = test_config("reopen");
+
+        {
+            let mut index = Index::new(config.clone()).unwrap();
+            index.insert("key1".to_string(), "record1".to_string()).await.unwrap();
+            index.flush().await.unwrap();
         }
-        Ok(false)
+
+        let reopened = Index::new(config).unwrap();
+        assert_eq!(reopened.get("key1").await.unwrap(), vec!["record1".to_string()]);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_bulk_load_is_queryable_immediately() {
+        let config = test_config("bulk_load");
+        let pairs = vec![
+            ("key1".to_string(), vec!["record1".to_string()]),
+            ("key2".to_string(), vec!["record2".to_string()]),
+        ];
+
+        let index = Index::bulk_load(config, pairs).unwrap();
+        assert_eq!(index.get("key1").await.unwrap(), vec!["record1".to_string()]);
+        assert_eq!(index.get("key2").await.unwrap(), vec!["record2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_intersect_returns_only_records_present_under_every_key() {
+        let mut index = Index::new(test_config("intersect")).unwrap();
+        index.insert("red".to_string(), "r1".to_string()).await.unwrap();
+        index.insert("red".to_string(), "r2".to_string()).await.unwrap();
+        index.insert("small".to_string(), "r2".to_string()).await.unwrap();
+        index.insert("small".to_string(), "r3".to_string()).await.unwrap();
+
+        assert_eq!(index.intersect(&["red", "small"]).unwrap(), vec!["r2".to_string()]);
+        assert_eq!(index.intersect(&[]).unwrap(), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn test_union_returns_records_present_under_any_key() {
+        let mut index = Index::new(test_config("union")).unwrap();
+        index.insert("red".to_string(), "r1".to_string()).await.unwrap();
+        index.insert("small".to_string(), "r2".to_string()).await.unwrap();
+
+        let mut records = index.union(&["red", "small"]).unwrap();
+        records.sort();
+        assert_eq!(records, vec!["r1".to_string(), "r2".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_range_yields_entries_in_ascending_key_order() {
+        let mut index = Index::new(test_config("range")).unwrap();
+        for (key, record) in [("b", "r2"), ("a", "r1"), ("c", "r3")] {
+            index.insert(key.to_string(), record.to_string()).await.unwrap();
+        }
+
+        let entries: Vec<(String, Vec<String>)> = index.range(Bound::Included("a"), Bound::Excluded("c")).unwrap().collect().await;
+        assert_eq!(entries, vec![("a".to_string(), vec!["r1".to_string()]), ("b".to_string(), vec!["r2".to_string()])]);
+    }
+
+    #[tokio::test]
+    async fn test_prefix_scan_stops_as_soon_as_the_prefix_no_longer_matches() {
+        let mut index = Index::new(test_config("prefix")).unwrap();
+        for (key, record) in [("app", "r1"), ("apple", "r2"), ("b", "r3")] {
+            index.insert(key.to_string(), record.to_string()).await.unwrap();
+        }
+
+        let entries: Vec<(String, Vec<String>)> = index.prefix_scan("app").unwrap().collect().await;
+        assert_eq!(
+            entries,
+            vec![("app".to_string(), vec!["r1".to_string()]), ("apple".to_string(), vec!["r2".to_string()])]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_interner_survives_reopen_so_ids_still_resolve() {
+        let config = test_config("interner_reopen");
+
+        {
+            let mut index = Index::new(config.clone()).unwrap();
+            index.insert("key1".to_string(), "record1".to_string()).await.unwrap();
+            index.flush().await.unwrap();
+        }
+
+        let reopened = Index::new(config).unwrap();
+        assert_eq!(reopened.get("key1").await.unwrap(), vec!["record1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_interner_sidecar_stays_in_step_with_an_auto_flush_before_any_explicit_flush() {
+        // flush_batch_size of 1 means every insert's staged page record
+        // auto-flushes the B-tree immediately, without anyone ever calling
+        // `Index::flush`. The interner sidecar must keep pace with that, or
+        // a "crash" (just dropping here, never flushing) before an explicit
+        // flush would leave newly interned ids unresolvable on reopen.
+        let config = IndexConfig { flush_batch_size: 1, ..test_config("auto_flush_interner") };
+
+        {
+            let mut index = Index::new(config.clone()).unwrap();
+            index.insert("key1".to_string(), "record1".to_string()).await.unwrap();
+            index.insert("key1".to_string(), "record2".to_string()).await.unwrap();
+            // Dropped without ever calling `index.flush()`.
+        }
+
+        let reopened = Index::new(config).unwrap();
+        let mut records = reopened.get("key1").await.unwrap();
+        records.sort();
+        assert_eq!(records, vec!["record1".to_string(), "record2".to_string()]);
+    }
+}
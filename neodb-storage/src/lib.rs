@@ -3,12 +3,19 @@
 //! This crate provides persistent storage capabilities for NeoDB,
 //! including disk-based storage, indexing, and data durability.
 
+pub mod backend;
+pub mod btree;
+pub mod convert;
 pub mod engine;
 pub mod index;
+pub mod metrics;
 pub mod persistence;
 
+pub use backend::{BackendKind, StorageBackend, Transaction};
+pub use convert::{convert_backend, ConversionReport};
 pub use engine::StorageEngine;
 pub use index::Index;
+pub use metrics::StorageMetrics;
 pub use persistence::PersistenceManager;
 
 /// Storage engine result type
@@ -19,16 +26,22 @@ pub type Result<T> = std::result::Result<T, StorageError>;
 pub enum StorageError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
-    
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
-    
+
     #[error("Storage not initialized")]
     NotInitialized,
-    
+
     #[error("Key not found: {0}")]
     KeyNotFound(String),
-    
+
     #[error("Index error: {0}")]
     Index(String),
+
+    #[error("Source and destination storage paths are identical: {0}")]
+    IdenticalStoragePaths(String),
+
+    #[error("Backend conversion key-count mismatch: source had {source_keys}, destination has {destination_keys}")]
+    ConversionMismatch { source_keys: u64, destination_keys: u64 },
 }
\ No newline at end of file
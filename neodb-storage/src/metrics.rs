@@ -0,0 +1,173 @@
+//! Prometheus-style metrics for `StorageEngine`
+//!
+//! Mirrors `CacheManager`'s `AtomicCacheStats` pattern: a handful of
+//! `AtomicU64` counters updated directly from `put`/`get`/`delete` (`exists`
+//! records nothing) without any locking, snapshotted into Prometheus text exposition format
+//! on demand rather than scraped by a separate agent.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Coarse latency buckets (in milliseconds) shared by every operation's
+/// histogram, matching Prometheus's own convention of cumulative `le`
+/// buckets ending in `+Inf`.
+const LATENCY_BUCKETS_MS: [f64; 6] = [1.0, 5.0, 25.0, 100.0, 500.0, 2500.0];
+
+/// A minimal cumulative histogram: one counter per bucket upper bound plus
+/// a running sum, enough to render Prometheus's `_bucket`/`_sum`/`_count`
+/// triad without pulling in a metrics crate.
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS_MS.len()],
+    sum_millis: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn observe(&self, duration: Duration) {
+        let millis = duration.as_secs_f64() * 1000.0;
+        for (bucket, &bound) in self.buckets.iter().zip(LATENCY_BUCKETS_MS.iter()) {
+            if millis <= bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.sum_millis.fetch_add(millis as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn render(&self, name: &str, op: &str, backend: &str, out: &mut String) {
+        let mut cumulative = 0u64;
+        for (bucket, &bound) in self.buckets.iter().zip(LATENCY_BUCKETS_MS.iter()) {
+            cumulative = cumulative.max(bucket.load(Ordering::Relaxed));
+            out.push_str(&format!(
+                "{name}_bucket{{operation=\"{op}\",backend=\"{backend}\",le=\"{bound}\"}} {cumulative}\n"
+            ));
+        }
+        out.push_str(&format!(
+            "{name}_bucket{{operation=\"{op}\",backend=\"{backend}\",le=\"+Inf\"}} {}\n",
+            self.count.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "{name}_sum{{operation=\"{op}\",backend=\"{backend}\"}} {}\n",
+            self.sum_millis.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "{name}_count{{operation=\"{op}\",backend=\"{backend}\"}} {}\n",
+            self.count.load(Ordering::Relaxed)
+        ));
+    }
+}
+
+/// Lock-free counters and latency histograms for a `StorageEngine`'s hot
+/// paths, rendered via `render_prometheus_text`.
+#[derive(Debug, Default)]
+pub struct StorageMetrics {
+    puts_total: AtomicU64,
+    gets_total: AtomicU64,
+    deletes_total: AtomicU64,
+    bytes_written_total: AtomicU64,
+    bytes_read_total: AtomicU64,
+    put_latency: Histogram,
+    get_latency: Histogram,
+    delete_latency: Histogram,
+}
+
+impl StorageMetrics {
+    pub fn record_put(&self, bytes: usize, duration: Duration) {
+        self.puts_total.fetch_add(1, Ordering::Relaxed);
+        self.bytes_written_total.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.put_latency.observe(duration);
+    }
+
+    pub fn record_get(&self, bytes_read: usize, duration: Duration) {
+        self.gets_total.fetch_add(1, Ordering::Relaxed);
+        self.bytes_read_total.fetch_add(bytes_read as u64, Ordering::Relaxed);
+        self.get_latency.observe(duration);
+    }
+
+    pub fn record_delete(&self, duration: Duration) {
+        self.deletes_total.fetch_add(1, Ordering::Relaxed);
+        self.delete_latency.observe(duration);
+    }
+
+    /// Total bytes ever passed to `record_put`, used to feed
+    /// `StorageStats::total_size_bytes`.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written_total.load(Ordering::Relaxed)
+    }
+
+    /// Render every counter/gauge/histogram in Prometheus text exposition
+    /// format, labeling each series with `backend` so multiple engines can
+    /// be scraped from the same process without colliding.
+    pub fn render_prometheus_text(&self, backend: &str, cache_hit_rate: f64) -> String {
+        let mut out = String::new();
+
+        out.push_str("# TYPE neodb_storage_ops_total counter\n");
+        for (op, value) in [
+            ("put", self.puts_total.load(Ordering::Relaxed)),
+            ("get", self.gets_total.load(Ordering::Relaxed)),
+            ("delete", self.deletes_total.load(Ordering::Relaxed)),
+        ] {
+            out.push_str(&format!(
+                "neodb_storage_ops_total{{operation=\"{op}\",backend=\"{backend}\"}} {value}\n"
+            ));
+        }
+
+        out.push_str("# TYPE neodb_storage_bytes_written_total counter\n");
+        out.push_str(&format!(
+            "neodb_storage_bytes_written_total{{backend=\"{backend}\"}} {}\n",
+            self.bytes_written_total.load(Ordering::Relaxed)
+        ));
+        out.push_str("# TYPE neodb_storage_bytes_read_total counter\n");
+        out.push_str(&format!(
+            "neodb_storage_bytes_read_total{{backend=\"{backend}\"}} {}\n",
+            self.bytes_read_total.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# TYPE neodb_storage_cache_hit_rate gauge\n");
+        out.push_str(&format!("neodb_storage_cache_hit_rate{{backend=\"{backend}\"}} {cache_hit_rate}\n"));
+
+        out.push_str("# TYPE neodb_storage_op_latency_milliseconds histogram\n");
+        self.put_latency.render("neodb_storage_op_latency_milliseconds", "put", backend, &mut out);
+        self.get_latency.render("neodb_storage_op_latency_milliseconds", "get", backend, &mut out);
+        self.delete_latency.render("neodb_storage_op_latency_milliseconds", "delete", backend, &mut out);
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_start_at_zero() {
+        let metrics = StorageMetrics::default();
+        let text = metrics.render_prometheus_text("embedded", 0.0);
+        assert!(text.contains("neodb_storage_ops_total{operation=\"put\",backend=\"embedded\"} 0"));
+        assert!(text.contains("neodb_storage_op_latency_milliseconds_count{operation=\"put\",backend=\"embedded\"} 0"));
+    }
+
+    #[test]
+    fn test_record_put_increments_counters_and_histogram() {
+        let metrics = StorageMetrics::default();
+        metrics.record_put(42, Duration::from_millis(2));
+        metrics.record_put(8, Duration::from_millis(2));
+
+        let text = metrics.render_prometheus_text("sqlite", 0.0);
+        assert!(text.contains("neodb_storage_ops_total{operation=\"put\",backend=\"sqlite\"} 2"));
+        assert!(text.contains("neodb_storage_bytes_written_total{backend=\"sqlite\"} 50"));
+        assert!(text.contains("neodb_storage_op_latency_milliseconds_count{operation=\"put\",backend=\"sqlite\"} 2"));
+    }
+
+    #[test]
+    fn test_latency_observation_lands_in_the_right_cumulative_bucket() {
+        let metrics = StorageMetrics::default();
+        metrics.record_get(4, Duration::from_millis(10));
+
+        let text = metrics.render_prometheus_text("embedded", 0.0);
+        // 10ms is past the 5ms bucket but within the 25ms bucket.
+        assert!(text.contains("neodb_storage_op_latency_milliseconds_bucket{operation=\"get\",backend=\"embedded\",le=\"5\"} 0"));
+        assert!(text.contains("neodb_storage_op_latency_milliseconds_bucket{operation=\"get\",backend=\"embedded\",le=\"25\"} 1"));
+    }
+}
@@ -1,8 +1,10 @@
 //! Persistence management for NeoDB storage
 
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
 use serde::{Serialize, Deserialize};
-use crate::{Result, StorageError};
+use crate::Result;
 
 /// Persistence configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +24,14 @@ impl Default for PersistenceConfig {
     }
 }
 
+/// A single mutation recorded in the write-ahead log, appended before it is
+/// applied to the backend and replayed on startup for crash recovery.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WalEntry {
+    Put { key: String, value: Vec<u8> },
+    Delete { key: String },
+}
+
 /// Persistence manager for coordinating snapshots and WAL
 #[derive(Debug)]
 pub struct PersistenceManager {
@@ -45,8 +55,125 @@ impl PersistenceManager {
         Ok(())
     }
 
-    pub async fn write_wal_entry(&self, _entry: &[u8]) -> Result<()> {
-        // TODO: Implement WAL writing
+    /// Append a mutation record to the write-ahead log at `wal_path` before
+    /// it is applied to the backend. A no-op if the WAL is disabled.
+    pub async fn append_wal_entry(&self, wal_path: &Path, entry: &WalEntry) -> Result<()> {
+        if !self.config.wal_enabled {
+            return Ok(());
+        }
+
+        let line = serde_json::to_string(entry)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(wal_path)?;
+        writeln!(file, "{line}")?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Read every entry still present in the WAL at `wal_path`, in the order
+    /// they were appended. Returns an empty list if the log doesn't exist
+    /// (e.g. a clean shutdown already checkpointed it).
+    pub async fn replay_wal(&self, wal_path: &Path) -> Result<Vec<WalEntry>> {
+        if !wal_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let reader = BufReader::new(File::open(wal_path)?);
+        let mut entries = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(serde_json::from_str(&line)?);
+        }
+        Ok(entries)
+    }
+
+    /// Truncate the WAL at `wal_path` now that its entries have been flushed
+    /// to the backend (or a snapshot). A no-op if the log doesn't exist.
+    pub async fn checkpoint(&self, wal_path: &Path) -> Result<()> {
+        if wal_path.exists() {
+            fs::remove_file(wal_path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::path::PathBuf;
+
+    fn temp_wal_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("neodb_persistence_test_{name}_{}.wal", std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn test_append_and_replay_wal_preserves_order() {
+        let manager = PersistenceManager::new(PersistenceConfig::default());
+        let wal_path = temp_wal_path("replay");
+        let _ = fs::remove_file(&wal_path);
+
+        manager
+            .append_wal_entry(&wal_path, &WalEntry::Put { key: "n:1".to_string(), value: b"alice".to_vec() })
+            .await
+            .unwrap();
+        manager
+            .append_wal_entry(&wal_path, &WalEntry::Delete { key: "n:1".to_string() })
+            .await
+            .unwrap();
+
+        let entries = manager.replay_wal(&wal_path).await.unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                WalEntry::Put { key: "n:1".to_string(), value: b"alice".to_vec() },
+                WalEntry::Delete { key: "n:1".to_string() },
+            ]
+        );
+
+        fs::remove_file(&wal_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_wal_missing_file_returns_empty() {
+        let manager = PersistenceManager::new(PersistenceConfig::default());
+        let wal_path = temp_wal_path("missing");
+        let _ = fs::remove_file(&wal_path);
+
+        assert_eq!(manager.replay_wal(&wal_path).await.unwrap(), Vec::new());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_truncates_wal() {
+        let manager = PersistenceManager::new(PersistenceConfig::default());
+        let wal_path = temp_wal_path("checkpoint");
+        let _ = fs::remove_file(&wal_path);
+
+        manager
+            .append_wal_entry(&wal_path, &WalEntry::Put { key: "n:1".to_string(), value: b"alice".to_vec() })
+            .await
+            .unwrap();
+        assert!(wal_path.exists());
+
+        manager.checkpoint(&wal_path).await.unwrap();
+        assert!(!wal_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_wal_does_not_write_entries() {
+        let manager = PersistenceManager::new(PersistenceConfig { wal_enabled: false, ..Default::default() });
+        let wal_path = temp_wal_path("disabled");
+        let _ = fs::remove_file(&wal_path);
+
+        manager
+            .append_wal_entry(&wal_path, &WalEntry::Put { key: "n:1".to_string(), value: b"alice".to_vec() })
+            .await
+            .unwrap();
+
+        assert!(!wal_path.exists());
+    }
+}
@@ -1,70 +1,114 @@
 //! Core traversal algorithms
 
-use std::collections::{HashSet, VecDeque};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use crate::visited::VisitedSet;
+use crate::visitor::WeightedVisitor;
 use crate::{Result, TraversalError, Visitor, VisitResult};
 
 /// Depth-First Search traversal
+///
+/// Holds its `VisitedSet` across calls to `traverse`, so repeated
+/// traversals of the same graph reuse already-interned node handles instead
+/// of rebuilding a string set from scratch each time.
 #[derive(Debug)]
 pub struct DepthFirstSearch {
     max_depth: Option<usize>,
-    visited: HashSet<String>,
+    visited: VisitedSet,
 }
 
 impl DepthFirstSearch {
     pub fn new() -> Self {
         Self {
             max_depth: None,
-            visited: HashSet::new(),
+            visited: VisitedSet::new(),
         }
     }
 
     pub fn with_max_depth(max_depth: usize) -> Self {
         Self {
             max_depth: Some(max_depth),
-            visited: HashSet::new(),
+            visited: VisitedSet::new(),
         }
     }
 
+    /// Traverse depth-first using an explicit `Vec` stack rather than the
+    /// call stack, so depth is bounded only by heap — a deep or adversarial
+    /// graph can no longer overflow and abort the process. Pre-order
+    /// (`visit_node`) fires on first arrival at a node, same as before;
+    /// post-order (`leave_node`) now fires once a node's entire subtree —
+    /// everything reachable through `get_neighbors` — has been exhausted,
+    /// enabling exit-ordering algorithms like topological sort that a
+    /// pre-order-only traversal can't express.
     pub fn traverse<V>(&mut self, start_node: &str, visitor: &mut V) -> Result<()>
     where
         V: Visitor,
     {
         self.visited.clear();
-        self.dfs_recursive(start_node, visitor, 0)
-    }
 
-    fn dfs_recursive<V>(&mut self, node_id: &str, visitor: &mut V, depth: usize) -> Result<()>
-    where
-        V: Visitor,
-    {
-        if let Some(max_depth) = self.max_depth {
-            if depth > max_depth {
-                return Err(TraversalError::DepthLimitExceeded { limit: max_depth });
-            }
+        if self.visited.is_marked_str(start_node) {
+            return Ok(());
         }
+        self.visited.mark_str(start_node);
 
-        if self.visited.contains(node_id) {
-            return Ok(());
+        let mut stack: Vec<Frame> = Vec::new();
+        match visitor.visit_node(start_node) {
+            VisitResult::Stop => return Ok(()),
+            VisitResult::Skip => {
+                visitor.leave_node(start_node);
+                return Ok(());
+            }
+            VisitResult::Continue => {
+                let neighbors = visitor.get_neighbors(start_node);
+                stack.push(Frame { node_id: start_node.to_string(), neighbors, next_index: 0, depth: 0 });
+            }
         }
 
-        self.visited.insert(node_id.to_string());
+        while let Some(frame) = stack.last_mut() {
+            if frame.next_index >= frame.neighbors.len() {
+                let finished = stack.pop().unwrap();
+                visitor.leave_node(&finished.node_id);
+                continue;
+            }
 
-        match visitor.visit_node(node_id) {
-            VisitResult::Continue => {
-                // Get neighbors from visitor and continue traversal
-                let neighbors = visitor.get_neighbors(node_id);
-                for neighbor in neighbors {
-                    self.dfs_recursive(&neighbor, visitor, depth + 1)?;
+            let neighbor = frame.neighbors[frame.next_index].clone();
+            frame.next_index += 1;
+            let depth = frame.depth + 1;
+
+            if let Some(max_depth) = self.max_depth {
+                if depth > max_depth {
+                    return Err(TraversalError::DepthLimitExceeded { limit: max_depth });
+                }
+            }
+
+            if self.visited.is_marked_str(&neighbor) {
+                continue;
+            }
+            self.visited.mark_str(&neighbor);
+
+            match visitor.visit_node(&neighbor) {
+                VisitResult::Stop => return Ok(()),
+                VisitResult::Skip => visitor.leave_node(&neighbor),
+                VisitResult::Continue => {
+                    let neighbors = visitor.get_neighbors(&neighbor);
+                    stack.push(Frame { node_id: neighbor, neighbors, next_index: 0, depth });
                 }
             }
-            VisitResult::Stop => return Ok(()),
-            VisitResult::Skip => {} // Skip this subtree but continue overall traversal
         }
 
         Ok(())
     }
 }
 
+/// One entry on `DepthFirstSearch::traverse`'s explicit stack: a node
+/// partway through visiting its neighbors.
+struct Frame {
+    node_id: String,
+    neighbors: Vec<String>,
+    next_index: usize,
+    depth: usize,
+}
+
 impl Default for DepthFirstSearch {
     fn default() -> Self {
         Self::new()
@@ -72,19 +116,25 @@ impl Default for DepthFirstSearch {
 }
 
 /// Breadth-First Search traversal
+///
+/// Holds its `VisitedSet` across calls to `traverse`, same as
+/// `DepthFirstSearch`, so repeated BFS runs over the same graph don't pay to
+/// rebuild a string set from scratch each time.
 #[derive(Debug)]
 pub struct BreadthFirstSearch {
     max_depth: Option<usize>,
+    visited: VisitedSet,
 }
 
 impl BreadthFirstSearch {
     pub fn new() -> Self {
-        Self { max_depth: None }
+        Self { max_depth: None, visited: VisitedSet::new() }
     }
 
     pub fn with_max_depth(max_depth: usize) -> Self {
         Self {
             max_depth: Some(max_depth),
+            visited: VisitedSet::new(),
         }
     }
 
@@ -92,11 +142,11 @@ impl BreadthFirstSearch {
     where
         V: Visitor,
     {
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::new();
+        self.visited.clear();
 
+        let mut queue = VecDeque::new();
         queue.push_back((start_node.to_string(), 0));
-        visited.insert(start_node.to_string());
+        self.visited.mark_str(start_node);
 
         while let Some((node_id, depth)) = queue.pop_front() {
             if let Some(max_depth) = self.max_depth {
@@ -109,8 +159,8 @@ impl BreadthFirstSearch {
                 VisitResult::Continue => {
                     let neighbors = visitor.get_neighbors(&node_id);
                     for neighbor in neighbors {
-                        if !visited.contains(&neighbor) {
-                            visited.insert(neighbor.clone());
+                        if !self.visited.is_marked_str(&neighbor) {
+                            self.visited.mark_str(&neighbor);
                             queue.push_back((neighbor, depth + 1));
                         }
                     }
@@ -130,6 +180,116 @@ impl Default for BreadthFirstSearch {
     }
 }
 
+/// Entry in `BestFirstSearch`'s frontier, ordered by ascending `priority`
+/// (`f = g + h`). Mirrors `path::HeapEntry`'s reversed-`BinaryHeap` trick to
+/// get min-heap behavior out of a max-heap.
+#[derive(Debug, Clone)]
+struct FrontierEntry {
+    priority: f32,
+    node: String,
+    g: f32,
+    depth: usize,
+}
+
+impl PartialEq for FrontierEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl Eq for FrontierEntry {}
+
+impl PartialOrd for FrontierEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrontierEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.partial_cmp(&self.priority).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Best-first / A*-guided traversal.
+///
+/// Visits nodes in order of `f = g + h`, where `g` is the accumulated edge
+/// cost from the start (via `WeightedVisitor::edge_cost`) and `h` is
+/// `WeightedVisitor::heuristic`'s estimate of the remaining cost. An
+/// always-zero heuristic degenerates to Dijkstra's algorithm. Honors
+/// `VisitResult::Stop`/`Skip`/`Continue` the same way
+/// `DepthFirstSearch`/`BreadthFirstSearch` do, and `max_depth` the way
+/// `BreadthFirstSearch` does: nodes past it are skipped, not reported as
+/// `TraversalError::DepthLimitExceeded`.
+#[derive(Debug)]
+pub struct BestFirstSearch {
+    max_depth: Option<usize>,
+}
+
+impl BestFirstSearch {
+    pub fn new() -> Self {
+        Self { max_depth: None }
+    }
+
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self { max_depth: Some(max_depth) }
+    }
+
+    pub fn traverse<V>(&mut self, start_node: &str, visitor: &mut V) -> Result<()>
+    where
+        V: WeightedVisitor,
+    {
+        let mut best_g: HashMap<String, f32> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        best_g.insert(start_node.to_string(), 0.0);
+        heap.push(FrontierEntry {
+            priority: visitor.heuristic(start_node),
+            node: start_node.to_string(),
+            g: 0.0,
+            depth: 0,
+        });
+
+        while let Some(FrontierEntry { node, g, depth, .. }) = heap.pop() {
+            // Stale entry left over from a since-improved g score.
+            if best_g.get(&node).is_some_and(|&best| g > best) {
+                continue;
+            }
+
+            if let Some(max_depth) = self.max_depth {
+                if depth > max_depth {
+                    continue;
+                }
+            }
+
+            match visitor.visit_node(&node) {
+                VisitResult::Stop => return Ok(()),
+                VisitResult::Skip => continue,
+                VisitResult::Continue => {
+                    for neighbor in visitor.get_neighbors(&node) {
+                        let tentative_g = g + visitor.edge_cost(&node, &neighbor);
+                        let improves = best_g.get(&neighbor).map(|&best| tentative_g < best).unwrap_or(true);
+
+                        if improves {
+                            best_g.insert(neighbor.clone(), tentative_g);
+                            let priority = tentative_g + visitor.heuristic(&neighbor);
+                            heap.push(FrontierEntry { priority, node: neighbor, g: tentative_g, depth: depth + 1 });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for BestFirstSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,6 +338,84 @@ mod tests {
         assert!(graph.visited_nodes.contains(&"A".to_string()));
     }
 
+    struct PostOrderGraph {
+        edges: HashMap<String, Vec<String>>,
+        visit_order: Vec<String>,
+        finish_order: Vec<String>,
+    }
+
+    impl PostOrderGraph {
+        fn new() -> Self {
+            let mut edges = HashMap::new();
+            edges.insert("A".to_string(), vec!["B".to_string(), "C".to_string()]);
+            edges.insert("B".to_string(), vec!["D".to_string()]);
+            edges.insert("C".to_string(), vec![]);
+            edges.insert("D".to_string(), vec![]);
+
+            Self { edges, visit_order: Vec::new(), finish_order: Vec::new() }
+        }
+    }
+
+    impl Visitor for PostOrderGraph {
+        fn visit_node(&mut self, node_id: &str) -> VisitResult {
+            self.visit_order.push(node_id.to_string());
+            VisitResult::Continue
+        }
+
+        fn get_neighbors(&self, node_id: &str) -> Vec<String> {
+            self.edges.get(node_id).cloned().unwrap_or_default()
+        }
+
+        fn leave_node(&mut self, node_id: &str) {
+            self.finish_order.push(node_id.to_string());
+        }
+    }
+
+    #[test]
+    fn test_iterative_dfs_fires_leave_node_only_after_a_subtree_is_exhausted() {
+        let mut dfs = DepthFirstSearch::new();
+        let mut graph = PostOrderGraph::new();
+
+        dfs.traverse("A", &mut graph).unwrap();
+
+        assert_eq!(graph.visit_order, vec!["A".to_string(), "B".to_string(), "D".to_string(), "C".to_string()]);
+        // D and C have no children, so they finish immediately; B only
+        // finishes after D does, and A only after both B and C do.
+        assert_eq!(graph.finish_order, vec!["D".to_string(), "B".to_string(), "C".to_string(), "A".to_string()]);
+    }
+
+    #[test]
+    fn test_iterative_dfs_does_not_overflow_the_call_stack_on_a_deep_chain() {
+        let mut edges = HashMap::new();
+        let depth = 50_000;
+        for i in 0..depth {
+            edges.insert(format!("n{i}"), vec![format!("n{}", i + 1)]);
+        }
+        edges.insert(format!("n{depth}"), vec![]);
+
+        struct ChainGraph {
+            edges: HashMap<String, Vec<String>>,
+            visited: usize,
+        }
+
+        impl Visitor for ChainGraph {
+            fn visit_node(&mut self, _node_id: &str) -> VisitResult {
+                self.visited += 1;
+                VisitResult::Continue
+            }
+
+            fn get_neighbors(&self, node_id: &str) -> Vec<String> {
+                self.edges.get(node_id).cloned().unwrap_or_default()
+            }
+        }
+
+        let mut graph = ChainGraph { edges, visited: 0 };
+        let mut dfs = DepthFirstSearch::new();
+        dfs.traverse("n0", &mut graph).unwrap();
+
+        assert_eq!(graph.visited, depth + 1);
+    }
+
     #[test]
     fn test_bfs_traversal() {
         let mut bfs = BreadthFirstSearch::new();
@@ -188,4 +426,114 @@ mod tests {
         assert!(!graph.visited_nodes.is_empty());
         assert!(graph.visited_nodes.contains(&"A".to_string()));
     }
+
+    #[test]
+    fn test_dfs_reuses_visited_set_across_calls() {
+        let mut dfs = DepthFirstSearch::new();
+        let mut graph = TestGraph::new();
+
+        dfs.traverse("A", &mut graph).unwrap();
+        graph.visited_nodes.clear();
+        dfs.traverse("A", &mut graph).unwrap();
+
+        // A second traversal on the same DepthFirstSearch must not be
+        // short-circuited by marks left over from the first call.
+        assert_eq!(graph.visited_nodes, vec!["A".to_string(), "B".to_string(), "D".to_string(), "C".to_string()]);
+    }
+
+    #[test]
+    fn test_bfs_reuses_visited_set_across_calls() {
+        let mut bfs = BreadthFirstSearch::new();
+        let mut graph = TestGraph::new();
+
+        bfs.traverse("A", &mut graph).unwrap();
+        graph.visited_nodes.clear();
+        bfs.traverse("A", &mut graph).unwrap();
+
+        assert_eq!(graph.visited_nodes, vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()]);
+    }
+
+    struct WeightedGraph {
+        edges: HashMap<String, Vec<(String, f32)>>,
+        heuristics: HashMap<String, f32>,
+        visited_nodes: Vec<String>,
+    }
+
+    impl WeightedGraph {
+        fn new() -> Self {
+            let mut edges = HashMap::new();
+            edges.insert("A".to_string(), vec![("B".to_string(), 1.0), ("C".to_string(), 5.0)]);
+            edges.insert("B".to_string(), vec![("D".to_string(), 1.0)]);
+            edges.insert("C".to_string(), vec![("D".to_string(), 1.0)]);
+            edges.insert("D".to_string(), vec![]);
+
+            let mut heuristics = HashMap::new();
+            heuristics.insert("A".to_string(), 2.0);
+            heuristics.insert("B".to_string(), 1.0);
+            heuristics.insert("C".to_string(), 1.0);
+            heuristics.insert("D".to_string(), 0.0);
+
+            Self { edges, heuristics, visited_nodes: Vec::new() }
+        }
+    }
+
+    impl Visitor for WeightedGraph {
+        fn visit_node(&mut self, node_id: &str) -> VisitResult {
+            self.visited_nodes.push(node_id.to_string());
+            VisitResult::Continue
+        }
+
+        fn get_neighbors(&self, node_id: &str) -> Vec<String> {
+            self.edges.get(node_id).cloned().unwrap_or_default().into_iter().map(|(n, _)| n).collect()
+        }
+    }
+
+    impl WeightedVisitor for WeightedGraph {
+        fn edge_cost(&self, from: &str, to: &str) -> f32 {
+            self.edges
+                .get(from)
+                .and_then(|neighbors| neighbors.iter().find(|(n, _)| n == to))
+                .map(|(_, cost)| *cost)
+                .unwrap_or(f32::INFINITY)
+        }
+
+        fn heuristic(&self, node: &str) -> f32 {
+            self.heuristics.get(node).copied().unwrap_or(0.0)
+        }
+    }
+
+    #[test]
+    fn test_best_first_search_visits_every_reachable_node() {
+        let mut search = BestFirstSearch::new();
+        let mut graph = WeightedGraph::new();
+
+        search.traverse("A", &mut graph).unwrap();
+
+        assert_eq!(graph.visited_nodes.first(), Some(&"A".to_string()));
+        assert!(graph.visited_nodes.contains(&"B".to_string()));
+        assert!(graph.visited_nodes.contains(&"C".to_string()));
+        assert!(graph.visited_nodes.contains(&"D".to_string()));
+    }
+
+    #[test]
+    fn test_best_first_search_reaches_cheaper_neighbor_before_pricier_one() {
+        let mut search = BestFirstSearch::new();
+        let mut graph = WeightedGraph::new();
+
+        search.traverse("A", &mut graph).unwrap();
+
+        let b_index = graph.visited_nodes.iter().position(|n| n == "B").unwrap();
+        let c_index = graph.visited_nodes.iter().position(|n| n == "C").unwrap();
+        assert!(b_index < c_index);
+    }
+
+    #[test]
+    fn test_best_first_search_honors_max_depth() {
+        let mut search = BestFirstSearch::with_max_depth(0);
+        let mut graph = WeightedGraph::new();
+
+        search.traverse("A", &mut graph).unwrap();
+
+        assert_eq!(graph.visited_nodes, vec!["A".to_string()]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,387 @@
+//! Dominator-tree analysis over an arbitrary graph
+//!
+//! Given a `start` node and a `get_neighbors` closure, computes the
+//! immediate dominator of every node reachable from `start` — the nearest
+//! node through which every path from `start` must pass. Uses the
+//! Cooper-Harvey-Kennedy iterative algorithm: a reverse-postorder
+//! numbering followed by fixpoint iteration, which converges faster in
+//! practice than solving the dominance equations directly.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::visitor::Visitor;
+
+/// One entry on `DominatorTree::dfs_postorder`'s explicit stack: a node
+/// partway through visiting its neighbors.
+struct PostorderFrame {
+    node: String,
+    neighbors: Vec<String>,
+    next_index: usize,
+}
+
+/// Dominator tree computed from a single `start` node.
+#[derive(Debug, Clone)]
+pub struct DominatorTree {
+    start: String,
+    idom: HashMap<String, String>,
+    rpo_number: HashMap<String, usize>,
+}
+
+impl DominatorTree {
+    /// Compute the dominator tree rooted at `start`, using `get_neighbors`
+    /// to discover the graph's forward edges. Nodes unreachable from
+    /// `start` are simply absent from the result.
+    pub fn compute<F>(start: &str, get_neighbors: F) -> Self
+    where
+        F: Fn(&str) -> Vec<String>,
+    {
+        let (rpo, predecessors) = Self::reverse_postorder(start, &get_neighbors);
+        let rpo_number: HashMap<String, usize> = rpo
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.clone(), i))
+            .collect();
+
+        let mut idom: HashMap<String, String> = HashMap::new();
+        idom.insert(start.to_string(), start.to_string());
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for node in rpo.iter().skip(1) {
+                let preds = predecessors.get(node).cloned().unwrap_or_default();
+                let mut new_idom: Option<String> = None;
+
+                for pred in &preds {
+                    if !idom.contains_key(pred) {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => pred.clone(),
+                        Some(current) => Self::intersect(&current, pred, &idom, &rpo_number),
+                    });
+                }
+
+                if let Some(new_idom) = new_idom {
+                    if idom.get(node) != Some(&new_idom) {
+                        idom.insert(node.clone(), new_idom);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        Self {
+            start: start.to_string(),
+            idom,
+            rpo_number,
+        }
+    }
+
+    /// Walk two finger pointers up the (partially built) dominator tree,
+    /// using RPO numbers to decide which finger to advance, until they meet.
+    fn intersect(
+        a: &str,
+        b: &str,
+        idom: &HashMap<String, String>,
+        rpo_number: &HashMap<String, usize>,
+    ) -> String {
+        let mut finger1 = a.to_string();
+        let mut finger2 = b.to_string();
+
+        while finger1 != finger2 {
+            while rpo_number[finger1.as_str()] > rpo_number[finger2.as_str()] {
+                finger1 = idom[finger1.as_str()].clone();
+            }
+            while rpo_number[finger2.as_str()] > rpo_number[finger1.as_str()] {
+                finger2 = idom[finger2.as_str()].clone();
+            }
+        }
+
+        finger1
+    }
+
+    /// DFS from `start`, returning nodes in reverse postorder along with
+    /// each reachable node's set of (reachable) predecessors.
+    fn reverse_postorder<F>(
+        start: &str,
+        get_neighbors: &F,
+    ) -> (Vec<String>, HashMap<String, Vec<String>>)
+    where
+        F: Fn(&str) -> Vec<String>,
+    {
+        let mut visited = HashSet::new();
+        let mut postorder = Vec::new();
+        let mut predecessors: HashMap<String, Vec<String>> = HashMap::new();
+
+        Self::dfs_postorder(start, get_neighbors, &mut visited, &mut postorder, &mut predecessors);
+
+        postorder.reverse();
+        (postorder, predecessors)
+    }
+
+    /// Iterative, explicit-`Vec`-stack postorder DFS — depth is bounded only
+    /// by heap rather than the call stack, so a deep or adversarial graph
+    /// can no longer overflow and abort the process (the same fix
+    /// `DepthFirstSearch::traverse` got in `algorithms.rs`, applied here
+    /// since `DominatorTree::compute` sits directly on top of this routine).
+    fn dfs_postorder<F>(
+        start: &str,
+        get_neighbors: &F,
+        visited: &mut HashSet<String>,
+        postorder: &mut Vec<String>,
+        predecessors: &mut HashMap<String, Vec<String>>,
+    ) where
+        F: Fn(&str) -> Vec<String>,
+    {
+        if visited.contains(start) {
+            return;
+        }
+        visited.insert(start.to_string());
+
+        let mut stack: Vec<PostorderFrame> = vec![PostorderFrame {
+            node: start.to_string(),
+            neighbors: get_neighbors(start),
+            next_index: 0,
+        }];
+
+        while let Some(frame) = stack.last_mut() {
+            if frame.next_index >= frame.neighbors.len() {
+                let finished = stack.pop().unwrap();
+                postorder.push(finished.node);
+                continue;
+            }
+
+            let current = frame.node.clone();
+            let neighbor = frame.neighbors[frame.next_index].clone();
+            frame.next_index += 1;
+
+            predecessors.entry(neighbor.clone()).or_default().push(current);
+
+            if visited.contains(&neighbor) {
+                continue;
+            }
+            visited.insert(neighbor.clone());
+
+            stack.push(PostorderFrame {
+                neighbors: get_neighbors(&neighbor),
+                node: neighbor,
+                next_index: 0,
+            });
+        }
+    }
+
+    /// The immediate dominator of `node`. `None` for `start` itself or for
+    /// nodes unreachable from `start`.
+    pub fn immediate_dominator(&self, node: &str) -> Option<&str> {
+        if node == self.start {
+            return None;
+        }
+        self.idom.get(node).map(|s| s.as_str())
+    }
+
+    /// All dominators of `node` (including `node` itself and `start`),
+    /// ordered from `node` up to `start`. Empty if `node` is unreachable.
+    pub fn dominators(&self, node: &str) -> Vec<String> {
+        if !self.rpo_number.contains_key(node) {
+            return Vec::new();
+        }
+
+        let mut result = vec![node.to_string()];
+        let mut current = node.to_string();
+
+        while current != self.start {
+            current = self.idom[current.as_str()].clone();
+            result.push(current.clone());
+        }
+
+        result
+    }
+
+    /// Whether `a` dominates `b`: every path from `start` to `b` passes
+    /// through `a`. A reachable node trivially dominates itself.
+    pub fn dominates(&self, a: &str, b: &str) -> bool {
+        if a == b {
+            return self.rpo_number.contains_key(a);
+        }
+        self.dominators(b).iter().any(|n| n == a)
+    }
+}
+
+/// Dominator-tree analysis driven by a `Visitor` rather than a bare
+/// closure, so it discovers edges through `Visitor::get_neighbors` and
+/// composes with the same visitors `DepthFirstSearch`/`BreadthFirstSearch`/
+/// `BestFirstSearch` take. Built directly on `DominatorTree`'s
+/// Cooper-Harvey-Kennedy computation — this is just a `Visitor`-shaped
+/// front door onto it.
+#[derive(Debug, Clone)]
+pub struct Dominators {
+    tree: DominatorTree,
+}
+
+impl Dominators {
+    /// Compute the immediate dominator of every node reachable from `start`.
+    pub fn compute<V>(start: &str, visitor: &V) -> Self
+    where
+        V: Visitor,
+    {
+        Self { tree: DominatorTree::compute(start, |node| visitor.get_neighbors(node)) }
+    }
+
+    /// The immediate dominator of `node`. `None` for `start` itself or for
+    /// nodes unreachable from `start`.
+    pub fn idom(&self, node: &str) -> Option<String> {
+        self.tree.immediate_dominator(node).map(str::to_string)
+    }
+
+    /// Whether `a` dominates `b`: every path from `start` to `b` passes
+    /// through `a`.
+    pub fn dominates(&self, a: &str, b: &str) -> bool {
+        self.tree.dominates(a, b)
+    }
+
+    /// The underlying dominator tree, for callers that want
+    /// `DominatorTree`'s full `dominators`/`immediate_dominator` API.
+    pub fn tree(&self) -> &DominatorTree {
+        &self.tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap as Map;
+
+    fn diamond_graph() -> Map<&'static str, Vec<&'static str>> {
+        // A -> B, A -> C, B -> D, C -> D
+        let mut graph = Map::new();
+        graph.insert("A", vec!["B", "C"]);
+        graph.insert("B", vec!["D"]);
+        graph.insert("C", vec!["D"]);
+        graph.insert("D", vec![]);
+        graph
+    }
+
+    fn neighbors_fn<'a>(graph: &'a Map<&'static str, Vec<&'static str>>) -> impl Fn(&str) -> Vec<String> + 'a {
+        move |node: &str| {
+            graph
+                .get(node)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|n| n.to_string())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_diamond_graph_idom_is_start() {
+        let graph = diamond_graph();
+        let tree = DominatorTree::compute("A", neighbors_fn(&graph));
+
+        assert_eq!(tree.immediate_dominator("D"), Some("A"));
+        assert_eq!(tree.immediate_dominator("B"), Some("A"));
+        assert_eq!(tree.immediate_dominator("C"), Some("A"));
+        assert_eq!(tree.immediate_dominator("A"), None);
+    }
+
+    #[test]
+    fn test_linear_chain_dominators() {
+        let mut graph = Map::new();
+        graph.insert("A", vec!["B"]);
+        graph.insert("B", vec!["C"]);
+        graph.insert("C", vec![]);
+
+        let tree = DominatorTree::compute("A", neighbors_fn(&graph));
+
+        assert_eq!(tree.dominators("C"), vec!["C", "B", "A"]);
+        assert!(tree.dominates("A", "C"));
+        assert!(tree.dominates("B", "C"));
+        assert!(!tree.dominates("C", "A"));
+    }
+
+    #[test]
+    fn test_single_point_of_failure_in_merge_graph() {
+        // A -> B -> D, A -> C -> D, D -> E. B and C are not single points
+        // of failure for E, but D is.
+        let mut graph = Map::new();
+        graph.insert("A", vec!["B", "C"]);
+        graph.insert("B", vec!["D"]);
+        graph.insert("C", vec!["D"]);
+        graph.insert("D", vec!["E"]);
+        graph.insert("E", vec![]);
+
+        let tree = DominatorTree::compute("A", neighbors_fn(&graph));
+
+        assert!(tree.dominates("D", "E"));
+        assert!(!tree.dominates("B", "E"));
+        assert!(!tree.dominates("C", "E"));
+    }
+
+    #[test]
+    fn test_unreachable_node_has_no_dominators() {
+        let mut graph = Map::new();
+        graph.insert("A", vec!["B"]);
+        graph.insert("B", vec![]);
+        graph.insert("Z", vec![]);
+
+        let tree = DominatorTree::compute("A", neighbors_fn(&graph));
+
+        assert!(tree.dominators("Z").is_empty());
+        assert_eq!(tree.immediate_dominator("Z"), None);
+    }
+
+    struct VisitorGraph {
+        edges: Map<&'static str, Vec<&'static str>>,
+    }
+
+    impl crate::visitor::Visitor for VisitorGraph {
+        fn visit_node(&mut self, _node_id: &str) -> crate::VisitResult {
+            crate::VisitResult::Continue
+        }
+
+        fn get_neighbors(&self, node_id: &str) -> Vec<String> {
+            self.edges.get(node_id).cloned().unwrap_or_default().into_iter().map(str::to_string).collect()
+        }
+    }
+
+    #[test]
+    fn test_compute_does_not_overflow_the_call_stack_on_a_deep_chain() {
+        let mut graph = Map::new();
+        let depth = 50_000;
+        for i in 0..depth {
+            graph.insert(format!("n{i}"), vec![format!("n{}", i + 1)]);
+        }
+        graph.insert(format!("n{depth}"), vec![]);
+
+        let get_neighbors = move |node: &str| graph.get(node).cloned().unwrap_or_default();
+        let tree = DominatorTree::compute("n0", get_neighbors);
+
+        // A straight chain's immediate dominator is always the direct
+        // predecessor, so the tail's dominators run all the way back to n0.
+        assert_eq!(
+            tree.immediate_dominator(&format!("n{depth}")),
+            Some(format!("n{}", depth - 1).as_str())
+        );
+        assert!(tree.dominates("n0", &format!("n{depth}")));
+    }
+
+    #[test]
+    fn test_dominators_via_visitor_matches_closure_based_computation() {
+        let mut edges = Map::new();
+        edges.insert("A", vec!["B", "C"]);
+        edges.insert("B", vec!["D"]);
+        edges.insert("C", vec!["D"]);
+        edges.insert("D", vec![]);
+        let graph = VisitorGraph { edges };
+
+        let dominators = Dominators::compute("A", &graph);
+
+        assert_eq!(dominators.idom("D"), Some("A".to_string()));
+        assert_eq!(dominators.idom("A"), None);
+        assert!(dominators.dominates("A", "D"));
+        assert!(!dominators.dominates("B", "D"));
+        assert_eq!(dominators.tree().dominators("D"), vec!["D".to_string(), "A".to_string()]);
+    }
+}
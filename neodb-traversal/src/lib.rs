@@ -4,12 +4,20 @@
 //! including DFS, BFS, shortest path, and advanced graph algorithms.
 
 pub mod algorithms;
+pub mod dominator;
+pub mod partition;
 pub mod path;
+pub mod spatial;
+pub mod visited;
 pub mod visitor;
 
-pub use algorithms::{DepthFirstSearch, BreadthFirstSearch};
+pub use algorithms::{BestFirstSearch, DepthFirstSearch, BreadthFirstSearch};
+pub use dominator::{Dominators, DominatorTree};
+pub use partition::{partition, PartitionResult};
 pub use path::{Path, PathFinder};
-pub use visitor::{Visitor, VisitResult};
+pub use spatial::SpatialIndex;
+pub use visited::{NodeHandle, VisitedSet};
+pub use visitor::{Visitor, VisitResult, WeightedVisitor};
 
 /// Traversal result type
 pub type Result<T> = std::result::Result<T, TraversalError>;
@@ -0,0 +1,466 @@
+//! Max-flow based graph partitioning
+//!
+//! Splits a node set into `k` roughly balanced shards while minimizing the
+//! number of edges cut between shards, so a graph can be spread across
+//! storage backends without every shard needing the full edge set
+//! replicated. Partitions are built by recursive bisection: each split
+//! picks two far-apart nodes as source/sink and computes a minimum s-t cut
+//! via Edmonds-Karp max-flow (edge weights become flow capacities — the
+//! residual graph's reachable set from the source is one side of the cut),
+//! then recurses on the largest remaining group until `k` shards exist.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::TraversalError;
+
+/// Result of partitioning a node set into shards.
+#[derive(Debug, Clone)]
+pub struct PartitionResult {
+    /// node_id -> shard index (`0..k`).
+    pub assignment: HashMap<String, usize>,
+    /// Number of distinct edges whose endpoints ended up in different shards.
+    pub cut_edges: usize,
+}
+
+impl PartitionResult {
+    /// List the `(from, to)` edges that cross shard boundaries, given the
+    /// same neighbor function used to build the partition — the set a
+    /// distributed layer would have to replicate or route.
+    pub fn cross_shard_edges<F>(&self, get_neighbors: F) -> Vec<(String, String)>
+    where
+        F: Fn(&str) -> Vec<(String, f64)>,
+    {
+        let mut cross = Vec::new();
+        for (node, &shard) in &self.assignment {
+            for (neighbor, _weight) in get_neighbors(node) {
+                if let Some(&other_shard) = self.assignment.get(&neighbor) {
+                    if other_shard != shard {
+                        cross.push((node.clone(), neighbor));
+                    }
+                }
+            }
+        }
+        cross
+    }
+}
+
+/// Assign every node in `nodes` to one of `k` shards, minimizing cut edges
+/// via recursive min-cut bisection, while keeping each shard within
+/// `⌈n/k⌉ * (1 + epsilon)` nodes. `get_neighbors` returns `(neighbor_id,
+/// edge_weight)` pairs, which become flow capacities during bisection.
+///
+/// Returns `Err(TraversalError::InvalidConfig)` if `k == 0`, since there is
+/// no way to assign nodes to zero shards.
+pub fn partition<F>(
+    nodes: &[String],
+    k: usize,
+    epsilon: f64,
+    get_neighbors: F,
+) -> Result<PartitionResult, TraversalError>
+where
+    F: Fn(&str) -> Vec<(String, f64)>,
+{
+    if k == 0 {
+        return Err(TraversalError::InvalidConfig(
+            "k must be at least 1".to_string(),
+        ));
+    }
+
+    let capacity_bound = (((nodes.len() as f64) / (k as f64)).ceil() * (1.0 + epsilon)).ceil() as usize;
+
+    let mut groups: Vec<Vec<String>> = vec![nodes.to_vec()];
+    while groups.len() < k {
+        let (largest_idx, _) = groups
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, g)| g.len())
+            .expect("groups is never empty");
+
+        let group = groups.swap_remove(largest_idx);
+        if group.len() <= 1 {
+            groups.push(group);
+            break;
+        }
+
+        let (side_a, side_b) = bisect(&group, &get_neighbors, capacity_bound);
+        if side_a.is_empty() || side_b.is_empty() {
+            // The group doesn't actually separate (e.g. every node is
+            // mutually reachable with no cut to exploit); stop trying to
+            // split it further.
+            groups.push(group);
+            break;
+        }
+
+        groups.push(side_a);
+        groups.push(side_b);
+    }
+
+    let mut assignment = HashMap::new();
+    for (shard, group) in groups.into_iter().enumerate() {
+        for node in group {
+            assignment.insert(node, shard);
+        }
+    }
+
+    let cut_edges = count_cut_edges(&assignment, &get_neighbors);
+    Ok(PartitionResult { assignment, cut_edges })
+}
+
+fn count_cut_edges<F>(assignment: &HashMap<String, usize>, get_neighbors: &F) -> usize
+where
+    F: Fn(&str) -> Vec<(String, f64)>,
+{
+    let mut seen = HashSet::new();
+    let mut cut = 0;
+    for (node, &shard) in assignment {
+        for (neighbor, _weight) in get_neighbors(node) {
+            if let Some(&other_shard) = assignment.get(&neighbor) {
+                if other_shard != shard {
+                    let key = if *node < neighbor {
+                        (node.clone(), neighbor)
+                    } else {
+                        (neighbor, node.clone())
+                    };
+                    if seen.insert(key) {
+                        cut += 1;
+                    }
+                }
+            }
+        }
+    }
+    cut
+}
+
+/// Split `group` into two sides using a minimum s-t cut: pick the two nodes
+/// with the largest hop distance as source/sink, run Edmonds-Karp max-flow
+/// over the induced subgraph, then read the cut off the residual graph's
+/// reachable set from the source. Falls back to an even split by position
+/// when `farthest_pair` finds no source/sink pair (e.g. fully disconnected
+/// nodes).
+fn bisect<F>(group: &[String], get_neighbors: &F, capacity_bound: usize) -> (Vec<String>, Vec<String>)
+where
+    F: Fn(&str) -> Vec<(String, f64)>,
+{
+    let members: HashSet<&String> = group.iter().collect();
+
+    let Some((source, sink)) = farthest_pair(group, get_neighbors, &members) else {
+        let mid = group.len() / 2;
+        return (group[..mid].to_vec(), group[mid..].to_vec());
+    };
+
+    let reachable = min_cut_reachable_set(group, get_neighbors, &members, &source, &sink);
+
+    let mut side_a: Vec<String> = group.iter().filter(|n| reachable.contains(*n)).cloned().collect();
+    let mut side_b: Vec<String> = group.iter().filter(|n| !reachable.contains(*n)).cloned().collect();
+
+    rebalance(&mut side_a, &mut side_b, capacity_bound);
+
+    (side_a, side_b)
+}
+
+/// Move nodes from whichever side exceeds `capacity_bound` to the other,
+/// best-effort — this can add a little to the cut but keeps shards from
+/// growing unboundedly large.
+fn rebalance(side_a: &mut Vec<String>, side_b: &mut Vec<String>, capacity_bound: usize) {
+    while side_a.len() > capacity_bound && side_b.len() < capacity_bound {
+        match side_a.pop() {
+            Some(node) => side_b.push(node),
+            None => break,
+        }
+    }
+    while side_b.len() > capacity_bound && side_a.len() < capacity_bound {
+        match side_b.pop() {
+            Some(node) => side_a.push(node),
+            None => break,
+        }
+    }
+}
+
+/// Unweighted hop distances from `start` to every node reachable within
+/// `members`.
+fn bfs_hops<F>(start: &str, get_neighbors: &F, members: &HashSet<&String>) -> HashMap<String, usize>
+where
+    F: Fn(&str) -> Vec<(String, f64)>,
+{
+    let mut dist = HashMap::new();
+    dist.insert(start.to_string(), 0usize);
+    let mut queue = VecDeque::new();
+    queue.push_back(start.to_string());
+
+    while let Some(node) = queue.pop_front() {
+        let d = dist[&node];
+        for (neighbor, _weight) in get_neighbors(&node) {
+            if members.contains(&neighbor) && !dist.contains_key(&neighbor) {
+                dist.insert(neighbor.clone(), d + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    dist
+}
+
+/// Double-BFS heuristic for a far-apart (source, sink) pair to bisect
+/// around. Returns `None` when the member farthest from `group`'s first
+/// node reaches no other member, which is always the case when `group` has
+/// no edges at all (and also when, say, the first node itself is isolated).
+fn farthest_pair<F>(
+    group: &[String],
+    get_neighbors: &F,
+    members: &HashSet<&String>,
+) -> Option<(String, String)>
+where
+    F: Fn(&str) -> Vec<(String, f64)>,
+{
+    let start = group.first()?;
+    let dist_from_start = bfs_hops(start, get_neighbors, members);
+    let (u, _) = dist_from_start.iter().max_by_key(|(_, &d)| d)?;
+    let u = u.clone();
+
+    let dist_from_u = bfs_hops(&u, get_neighbors, members);
+    let (v, _) = dist_from_u.iter().max_by_key(|(_, &d)| d)?;
+    let v = v.clone();
+
+    if u == v {
+        return None;
+    }
+    Some((u, v))
+}
+
+/// Directed residual capacity graph restricted to edges between `members`.
+fn build_capacity_graph<F>(
+    group: &[String],
+    get_neighbors: &F,
+    members: &HashSet<&String>,
+) -> HashMap<String, HashMap<String, f64>>
+where
+    F: Fn(&str) -> Vec<(String, f64)>,
+{
+    let mut capacity: HashMap<String, HashMap<String, f64>> = HashMap::new();
+    for node in group {
+        let entry = capacity.entry(node.clone()).or_default();
+        for (neighbor, weight) in get_neighbors(node) {
+            if members.contains(&neighbor) {
+                *entry.entry(neighbor).or_insert(0.0) += weight.max(0.0);
+            }
+        }
+    }
+    for node in group {
+        capacity.entry(node.clone()).or_default();
+    }
+    capacity
+}
+
+/// Find an augmenting path from `source` to `sink` via BFS (the "Edmonds"
+/// part of Edmonds-Karp — breadth-first, so the shortest augmenting path is
+/// used, bounding the number of augmentations polynomially).
+fn bfs_augmenting_path(
+    capacity: &HashMap<String, HashMap<String, f64>>,
+    source: &str,
+    sink: &str,
+) -> Option<(Vec<String>, f64)> {
+    let mut parent: HashMap<String, String> = HashMap::new();
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(source.to_string());
+    visited.insert(source.to_string());
+
+    while let Some(node) = queue.pop_front() {
+        if node == sink {
+            break;
+        }
+        if let Some(edges) = capacity.get(&node) {
+            for (next, &cap) in edges {
+                if cap > 1e-9 && !visited.contains(next) {
+                    visited.insert(next.clone());
+                    parent.insert(next.clone(), node.clone());
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+    }
+
+    if !visited.contains(sink) {
+        return None;
+    }
+
+    let mut path = vec![sink.to_string()];
+    let mut cursor = sink.to_string();
+    while cursor != source {
+        let prev = parent.get(&cursor)?.clone();
+        path.push(prev.clone());
+        cursor = prev;
+    }
+    path.reverse();
+
+    let mut bottleneck = f64::INFINITY;
+    for window in path.windows(2) {
+        let cap = capacity
+            .get(&window[0])
+            .and_then(|edges| edges.get(&window[1]))
+            .copied()
+            .unwrap_or(0.0);
+        bottleneck = bottleneck.min(cap);
+    }
+
+    Some((path, bottleneck))
+}
+
+/// Run Edmonds-Karp max-flow from `source` to `sink` over the subgraph
+/// induced by `group`, then return the set of nodes still reachable from
+/// `source` in the final residual graph — the source side of a minimum cut.
+fn min_cut_reachable_set<F>(
+    group: &[String],
+    get_neighbors: &F,
+    members: &HashSet<&String>,
+    source: &str,
+    sink: &str,
+) -> HashSet<String>
+where
+    F: Fn(&str) -> Vec<(String, f64)>,
+{
+    let mut capacity = build_capacity_graph(group, get_neighbors, members);
+
+    while let Some((path, bottleneck)) = bfs_augmenting_path(&capacity, source, sink) {
+        for window in path.windows(2) {
+            let (u, v) = (&window[0], &window[1]);
+            *capacity.get_mut(u).expect("u visited during BFS").get_mut(v).expect("edge on augmenting path") -= bottleneck;
+            let reverse = capacity.entry(v.clone()).or_default();
+            *reverse.entry(u.clone()).or_insert(0.0) += bottleneck;
+        }
+    }
+
+    let mut reachable = HashSet::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(source.to_string());
+    reachable.insert(source.to_string());
+    while let Some(node) = queue.pop_front() {
+        if let Some(edges) = capacity.get(&node) {
+            for (next, &cap) in edges {
+                if cap > 1e-9 && !reachable.contains(next) {
+                    reachable.insert(next.clone());
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+    }
+
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two dense triangles joined by a single bridge edge — the cheapest
+    /// cut is the bridge itself.
+    fn bridge_graph() -> HashMap<String, Vec<(String, f64)>> {
+        let mut g: HashMap<String, Vec<(String, f64)>> = HashMap::new();
+        let edges = [
+            ("a1", "a2", 5.0), ("a2", "a1", 5.0),
+            ("a2", "a3", 5.0), ("a3", "a2", 5.0),
+            ("a1", "a3", 5.0), ("a3", "a1", 5.0),
+            ("b1", "b2", 5.0), ("b2", "b1", 5.0),
+            ("b2", "b3", 5.0), ("b3", "b2", 5.0),
+            ("b1", "b3", 5.0), ("b3", "b1", 5.0),
+            ("a1", "b1", 1.0), ("b1", "a1", 1.0),
+        ];
+        for (from, to, weight) in edges {
+            g.entry(from.to_string()).or_default().push((to.to_string(), weight));
+        }
+        g
+    }
+
+    #[test]
+    fn test_partition_into_one_shard_is_a_no_op() {
+        let nodes: Vec<String> = vec!["a1", "a2", "a3"].into_iter().map(String::from).collect();
+        let graph = bridge_graph();
+        let get_neighbors = |n: &str| graph.get(n).cloned().unwrap_or_default();
+
+        let result = partition(&nodes, 1, 0.5, get_neighbors).unwrap();
+        assert!(nodes.iter().all(|n| result.assignment[n] == 0));
+    }
+
+    #[test]
+    fn test_partition_into_zero_shards_returns_invalid_config_error() {
+        let nodes: Vec<String> = vec!["a1", "a2", "a3"].into_iter().map(String::from).collect();
+        let graph = bridge_graph();
+        let get_neighbors = |n: &str| graph.get(n).cloned().unwrap_or_default();
+
+        let result = partition(&nodes, 0, 0.5, get_neighbors);
+        assert!(matches!(result, Err(TraversalError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_partition_splits_along_bridge_edge() {
+        let graph = bridge_graph();
+        let nodes: Vec<String> = graph.keys().cloned().collect();
+        let get_neighbors = |n: &str| graph.get(n).cloned().unwrap_or_default();
+
+        let result = partition(&nodes, 2, 0.5, get_neighbors).unwrap();
+
+        // Every a-node must land in the same shard, every b-node in the
+        // other, since splitting a triangle costs strictly more than
+        // cutting the single bridge edge.
+        let a_shard = result.assignment["a1"];
+        let b_shard = result.assignment["b1"];
+        assert_ne!(a_shard, b_shard);
+        for node in ["a1", "a2", "a3"] {
+            assert_eq!(result.assignment[node], a_shard);
+        }
+        for node in ["b1", "b2", "b3"] {
+            assert_eq!(result.assignment[node], b_shard);
+        }
+        assert_eq!(result.cut_edges, 1);
+    }
+
+    #[test]
+    fn test_cross_shard_edges_lists_only_boundary_crossings() {
+        let graph = bridge_graph();
+        let nodes: Vec<String> = graph.keys().cloned().collect();
+        let get_neighbors = |n: &str| graph.get(n).cloned().unwrap_or_default();
+
+        let result = partition(&nodes, 2, 0.5, get_neighbors).unwrap();
+        let crossing = result.cross_shard_edges(get_neighbors);
+
+        assert_eq!(crossing.len(), 2); // a1->b1 and b1->a1, both directed
+        for (from, to) in &crossing {
+            let endpoints = [from.as_str(), to.as_str()];
+            assert!(endpoints.contains(&"a1"));
+            assert!(endpoints.contains(&"b1"));
+        }
+    }
+
+    #[test]
+    fn test_partition_respects_capacity_bound() {
+        let graph = bridge_graph();
+        let nodes: Vec<String> = graph.keys().cloned().collect();
+        let get_neighbors = |n: &str| graph.get(n).cloned().unwrap_or_default();
+
+        let result = partition(&nodes, 3, 0.34, get_neighbors).unwrap();
+        let bound = ((nodes.len() as f64 / 3.0).ceil() * 1.34).ceil() as usize;
+
+        let mut counts = HashMap::new();
+        for &shard in result.assignment.values() {
+            *counts.entry(shard).or_insert(0) += 1;
+        }
+        for count in counts.values() {
+            assert!(*count <= bound, "shard of size {count} exceeds bound {bound}");
+        }
+    }
+
+    #[test]
+    fn test_partition_disconnected_nodes_falls_back_to_even_split() {
+        let nodes: Vec<String> = (0..4).map(|i| format!("n{i}")).collect();
+        let get_neighbors = |_: &str| Vec::new();
+
+        let result = partition(&nodes, 2, 0.5, get_neighbors).unwrap();
+        assert_eq!(result.assignment.len(), 4);
+
+        let mut counts = HashMap::new();
+        for &shard in result.assignment.values() {
+            *counts.entry(shard).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 2);
+    }
+}
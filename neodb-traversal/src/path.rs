@@ -1,6 +1,7 @@
 //! Path finding algorithms
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use crate::{Result, TraversalError};
 
 /// Represents a path between nodes
@@ -145,6 +146,7 @@ impl PathFinder {
         Ok(paths)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn find_all_paths_recursive<F>(
         &self,
         current: &str,
@@ -200,6 +202,254 @@ impl Default for PathFinder {
     }
 }
 
+/// Entry in the Dijkstra/A* frontier, ordered by ascending `priority` (`f = g + h`).
+///
+/// `f64` is not `Ord`, so we wrap it and reverse the comparison to turn
+/// `BinaryHeap`'s default max-heap behavior into a min-heap.
+#[derive(Debug, Clone)]
+struct HeapEntry {
+    priority: f64,
+    node: String,
+    depth: usize,
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl Eq for HeapEntry {}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .partial_cmp(&self.priority)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PathFinder {
+    /// Find the lowest-cost path between two nodes using Dijkstra's algorithm.
+    ///
+    /// `get_weighted_neighbors` returns `(neighbor_id, edge_cost)` pairs; edge
+    /// costs must be non-negative.
+    pub fn find_shortest_path_weighted<F>(
+        &self,
+        start: &str,
+        end: &str,
+        get_weighted_neighbors: F,
+    ) -> Result<Option<Path>>
+    where
+        F: Fn(&str) -> Vec<(String, f64)>,
+    {
+        self.weighted_search(start, end, get_weighted_neighbors, |_| 0.0)
+    }
+
+    /// Find the lowest-cost path between two nodes using A*.
+    ///
+    /// `heuristic` must be an admissible lower-bound estimate of the
+    /// remaining cost from a node to `end`; an always-zero heuristic
+    /// degenerates to Dijkstra.
+    pub fn find_shortest_path_astar<F, H>(
+        &self,
+        start: &str,
+        end: &str,
+        get_weighted_neighbors: F,
+        heuristic: H,
+    ) -> Result<Option<Path>>
+    where
+        F: Fn(&str) -> Vec<(String, f64)>,
+        H: Fn(&str) -> f64,
+    {
+        self.weighted_search(start, end, get_weighted_neighbors, heuristic)
+    }
+
+    fn weighted_search<F, H>(
+        &self,
+        start: &str,
+        end: &str,
+        get_weighted_neighbors: F,
+        heuristic: H,
+    ) -> Result<Option<Path>>
+    where
+        F: Fn(&str) -> Vec<(String, f64)>,
+        H: Fn(&str) -> f64,
+    {
+        if start == end {
+            return Ok(Some(Path::with_nodes(vec![start.to_string()])));
+        }
+
+        let mut g_score: HashMap<String, f64> = HashMap::new();
+        let mut parent: HashMap<String, String> = HashMap::new();
+        let mut finalized: HashSet<String> = HashSet::new();
+        let mut heap = BinaryHeap::new();
+
+        g_score.insert(start.to_string(), 0.0);
+        heap.push(HeapEntry {
+            priority: heuristic(start),
+            node: start.to_string(),
+            depth: 0,
+        });
+
+        while let Some(HeapEntry { node: current, depth, .. }) = heap.pop() {
+            // Stale entry left over from a since-improved g_score.
+            if finalized.contains(&current) {
+                continue;
+            }
+            finalized.insert(current.clone());
+
+            if current == end {
+                let mut path_nodes = Vec::new();
+                let mut cursor = end.to_string();
+                path_nodes.push(cursor.clone());
+
+                while let Some(prev) = parent.get(&cursor) {
+                    path_nodes.push(prev.clone());
+                    cursor = prev.clone();
+                }
+
+                path_nodes.reverse();
+                let mut path = Path::with_nodes(path_nodes);
+                path.total_cost = g_score[end];
+                return Ok(Some(path));
+            }
+
+            if let Some(max_depth) = self.max_depth {
+                if depth >= max_depth {
+                    continue;
+                }
+            }
+
+            let current_g = g_score[&current];
+            for (neighbor, edge_cost) in get_weighted_neighbors(&current) {
+                if edge_cost < 0.0 {
+                    return Err(TraversalError::InvalidConfig(format!(
+                        "negative edge weight {} on edge {} -> {}",
+                        edge_cost, current, neighbor
+                    )));
+                }
+
+                if finalized.contains(&neighbor) {
+                    continue;
+                }
+
+                let tentative_g = current_g + edge_cost;
+                let improves = g_score
+                    .get(&neighbor)
+                    .map(|&best| tentative_g < best)
+                    .unwrap_or(true);
+
+                if improves {
+                    g_score.insert(neighbor.clone(), tentative_g);
+                    parent.insert(neighbor.clone(), current.clone());
+                    heap.push(HeapEntry {
+                        priority: tentative_g + heuristic(&neighbor),
+                        node: neighbor,
+                        depth: depth + 1,
+                    });
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Approximate shortest path search with bounded memory.
+    ///
+    /// Unlike [`PathFinder::find_shortest_path_astar`], which keeps a
+    /// priority queue over every discovered node, beam search retains only
+    /// the best `beam_width` candidates at each layer, trading optimality for
+    /// a frontier that never grows past `beam_width` — suitable for graphs
+    /// too large to hold a full search frontier in memory. Results are
+    /// **approximate**: a `beam_width` smaller than the graph's true
+    /// branching factor can prune away the optimal path. A `beam_width` of
+    /// `usize::MAX` degenerates to greedy best-first search (no pruning).
+    pub fn find_path_beam<F, H>(
+        &self,
+        start: &str,
+        end: &str,
+        get_weighted_neighbors: F,
+        heuristic: H,
+        beam_width: usize,
+    ) -> Result<Option<Path>>
+    where
+        F: Fn(&str) -> Vec<(String, f64)>,
+        H: Fn(&str) -> f64,
+    {
+        if start == end {
+            return Ok(Some(Path::with_nodes(vec![start.to_string()])));
+        }
+
+        let mut parent: HashMap<String, String> = HashMap::new();
+        let mut frontier: Vec<(String, f64)> = vec![(start.to_string(), 0.0)];
+
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            if let Some(max_depth) = self.max_depth {
+                if depth >= max_depth {
+                    break;
+                }
+            }
+
+            let mut candidates: HashMap<String, f64> = HashMap::new();
+            for (node, accumulated_cost) in &frontier {
+                for (neighbor, edge_cost) in get_weighted_neighbors(node) {
+                    let candidate_cost = accumulated_cost + edge_cost;
+                    let is_cheapest = candidates
+                        .get(&neighbor)
+                        .map(|&best| candidate_cost < best)
+                        .unwrap_or(true);
+
+                    if is_cheapest {
+                        candidates.insert(neighbor.clone(), candidate_cost);
+                        parent.insert(neighbor.clone(), node.clone());
+                    }
+                }
+            }
+
+            if candidates.is_empty() {
+                break;
+            }
+
+            if let Some(&end_cost) = candidates.get(end) {
+                let mut path_nodes = Vec::new();
+                let mut cursor = end.to_string();
+                path_nodes.push(cursor.clone());
+                while let Some(prev) = parent.get(&cursor) {
+                    path_nodes.push(prev.clone());
+                    cursor = prev.clone();
+                }
+                path_nodes.reverse();
+
+                let mut path = Path::with_nodes(path_nodes);
+                path.total_cost = end_cost;
+                return Ok(Some(path));
+            }
+
+            let mut ranked: Vec<(String, f64)> = candidates.into_iter().collect();
+            ranked.sort_by(|(a_node, a_cost), (b_node, b_cost)| {
+                let a_f = a_cost + heuristic(a_node);
+                let b_f = b_cost + heuristic(b_node);
+                a_f.partial_cmp(&b_f).unwrap_or(Ordering::Equal)
+            });
+            ranked.truncate(beam_width);
+
+            frontier = ranked;
+            depth += 1;
+        }
+
+        Ok(None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,4 +498,94 @@ mod tests {
 
         assert!(path.is_none());
     }
+
+    fn create_weighted_test_graph() -> HashMap<String, Vec<(String, f64)>> {
+        let mut graph = HashMap::new();
+        graph.insert("A".to_string(), vec![("B".to_string(), 1.0), ("C".to_string(), 5.0)]);
+        graph.insert("B".to_string(), vec![("D".to_string(), 1.0)]);
+        graph.insert("C".to_string(), vec![("D".to_string(), 1.0)]);
+        graph.insert("D".to_string(), vec![]);
+        graph
+    }
+
+    #[test]
+    fn test_shortest_path_weighted_prefers_cheaper_route() {
+        let graph = create_weighted_test_graph();
+        let path_finder = PathFinder::new();
+
+        let get_weighted_neighbors =
+            |node: &str| -> Vec<(String, f64)> { graph.get(node).cloned().unwrap_or_default() };
+
+        let path = path_finder
+            .find_shortest_path_weighted("A", "D", get_weighted_neighbors)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path.nodes, vec!["A".to_string(), "B".to_string(), "D".to_string()]);
+        assert_eq!(path.total_cost, 2.0);
+    }
+
+    #[test]
+    fn test_shortest_path_weighted_rejects_negative_weights() {
+        let mut graph = HashMap::new();
+        graph.insert("A".to_string(), vec![("B".to_string(), -1.0)]);
+        graph.insert("B".to_string(), vec![]);
+        let path_finder = PathFinder::new();
+
+        let get_weighted_neighbors =
+            |node: &str| -> Vec<(String, f64)> { graph.get(node).cloned().unwrap_or_default() };
+
+        let result = path_finder.find_shortest_path_weighted("A", "B", get_weighted_neighbors);
+        assert!(matches!(result, Err(TraversalError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_shortest_path_astar_matches_dijkstra_with_zero_heuristic() {
+        let graph = create_weighted_test_graph();
+        let path_finder = PathFinder::new();
+
+        let get_weighted_neighbors =
+            |node: &str| -> Vec<(String, f64)> { graph.get(node).cloned().unwrap_or_default() };
+
+        let path = path_finder
+            .find_shortest_path_astar("A", "D", get_weighted_neighbors, |_| 0.0)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path.total_cost, 2.0);
+        assert_eq!(path.nodes.last(), Some(&"D".to_string()));
+    }
+
+    #[test]
+    fn test_beam_search_finds_path_within_width() {
+        let graph = create_weighted_test_graph();
+        let path_finder = PathFinder::new();
+
+        let get_weighted_neighbors =
+            |node: &str| -> Vec<(String, f64)> { graph.get(node).cloned().unwrap_or_default() };
+
+        let path = path_finder
+            .find_path_beam("A", "D", get_weighted_neighbors, |_| 0.0, 1)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path.nodes.first(), Some(&"A".to_string()));
+        assert_eq!(path.nodes.last(), Some(&"D".to_string()));
+    }
+
+    #[test]
+    fn test_beam_search_max_width_is_greedy_best_first() {
+        let graph = create_weighted_test_graph();
+        let path_finder = PathFinder::new();
+
+        let get_weighted_neighbors =
+            |node: &str| -> Vec<(String, f64)> { graph.get(node).cloned().unwrap_or_default() };
+
+        let path = path_finder
+            .find_path_beam("A", "D", get_weighted_neighbors, |_| 0.0, usize::MAX)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path.total_cost, 2.0);
+    }
 }
\ No newline at end of file
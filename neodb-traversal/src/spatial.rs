@@ -0,0 +1,572 @@
+//! Spatial indexing for coordinate-bearing graph nodes
+//!
+//! Backs "nearest neighbors" and "within a region" queries with a
+//! bulk-mutable R-tree, keyed by node ID and an `[f64; DIM]` coordinate.
+//! `DIM` is a const generic so the same tree works for 2D (e.g.
+//! geographic lat/lon) or 3D (e.g. embedding-space) graphs.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+const MAX_ENTRIES: usize = 8;
+const MIN_ENTRIES: usize = MAX_ENTRIES / 2;
+
+/// An axis-aligned bounding box in `DIM`-dimensional space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect<const DIM: usize> {
+    min: [f64; DIM],
+    max: [f64; DIM],
+}
+
+impl<const DIM: usize> Rect<DIM> {
+    fn from_point(point: [f64; DIM]) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    fn union(&self, other: &Rect<DIM>) -> Rect<DIM> {
+        let mut min = self.min;
+        let mut max = self.max;
+        for i in 0..DIM {
+            min[i] = min[i].min(other.min[i]);
+            max[i] = max[i].max(other.max[i]);
+        }
+        Rect { min, max }
+    }
+
+    /// "Area" generalized to `DIM` dimensions (hyper-volume).
+    fn area(&self) -> f64 {
+        (0..DIM).map(|i| self.max[i] - self.min[i]).product()
+    }
+
+    fn enlargement(&self, other: &Rect<DIM>) -> f64 {
+        self.union(other).area() - self.area()
+    }
+
+    fn intersects(&self, other: &Rect<DIM>) -> bool {
+        (0..DIM).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+
+    fn contains_point(&self, point: &[f64; DIM]) -> bool {
+        (0..DIM).all(|i| point[i] >= self.min[i] && point[i] <= self.max[i])
+    }
+
+    /// Squared distance from `point` to the nearest point on/in this box,
+    /// zero if `point` is inside. Used to prioritize R-tree search order
+    /// without paying for a square root until a candidate is confirmed.
+    fn min_dist_sq(&self, point: &[f64; DIM]) -> f64 {
+        (0..DIM)
+            .map(|i| {
+                let d = if point[i] < self.min[i] {
+                    self.min[i] - point[i]
+                } else if point[i] > self.max[i] {
+                    point[i] - self.max[i]
+                } else {
+                    0.0
+                };
+                d * d
+            })
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Entry<const DIM: usize> {
+    Leaf { rect: Rect<DIM>, node_id: String },
+    Branch { rect: Rect<DIM>, child: Box<RNode<DIM>> },
+}
+
+impl<const DIM: usize> Entry<DIM> {
+    fn rect(&self) -> &Rect<DIM> {
+        match self {
+            Entry::Leaf { rect, .. } => rect,
+            Entry::Branch { rect, .. } => rect,
+        }
+    }
+}
+
+/// Entry in the [`SpatialIndex::nearest_neighbors`] best-first search queue,
+/// ordered by ascending `dist_sq` (smallest-first out of a max-heap).
+enum NnItem<'a, const DIM: usize> {
+    Subtree(&'a RNode<DIM>),
+    Point(&'a str),
+}
+
+struct NnCandidate<'a, const DIM: usize> {
+    dist_sq: f64,
+    item: NnItem<'a, DIM>,
+}
+
+impl<const DIM: usize> PartialEq for NnCandidate<'_, DIM> {
+    fn eq(&self, other: &Self) -> bool {
+        self.dist_sq == other.dist_sq
+    }
+}
+
+impl<const DIM: usize> Eq for NnCandidate<'_, DIM> {}
+
+impl<const DIM: usize> PartialOrd for NnCandidate<'_, DIM> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const DIM: usize> Ord for NnCandidate<'_, DIM> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist_sq
+            .partial_cmp(&self.dist_sq)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct RNode<const DIM: usize> {
+    is_leaf: bool,
+    entries: Vec<Entry<DIM>>,
+}
+
+impl<const DIM: usize> RNode<DIM> {
+    fn leaf() -> Self {
+        Self {
+            is_leaf: true,
+            entries: Vec::new(),
+        }
+    }
+
+    fn bounding_rect(&self) -> Option<Rect<DIM>> {
+        let mut iter = self.entries.iter();
+        let first = *iter.next()?.rect();
+        Some(iter.fold(first, |acc, e| acc.union(e.rect())))
+    }
+}
+
+/// An in-memory R-tree mapping node IDs to `[f64; DIM]` coordinates.
+#[derive(Debug)]
+pub struct SpatialIndex<const DIM: usize> {
+    root: RNode<DIM>,
+    len: usize,
+    // Reverse lookup from node ID to coordinate, so a straight-line
+    // heuristic can look up a node's point in O(1) instead of walking the
+    // tree; kept in sync by `insert`/`remove`.
+    points: HashMap<String, [f64; DIM]>,
+}
+
+impl<const DIM: usize> Default for SpatialIndex<DIM> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const DIM: usize> SpatialIndex<DIM> {
+    pub fn new() -> Self {
+        Self {
+            root: RNode::leaf(),
+            len: 0,
+            points: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Insert or update a node's coordinate.
+    pub fn insert(&mut self, node_id: impl Into<String>, point: [f64; DIM]) {
+        let node_id = node_id.into();
+        self.remove(&node_id);
+        self.points.insert(node_id.clone(), point);
+
+        let leaf_entry = Entry::Leaf {
+            rect: Rect::from_point(point),
+            node_id,
+        };
+
+        if let Some(split) = Self::insert_entry(&mut self.root, leaf_entry) {
+            let mut new_root = RNode {
+                is_leaf: false,
+                entries: Vec::new(),
+            };
+            let old_root = std::mem::replace(&mut self.root, RNode::leaf());
+            let old_rect = old_root.bounding_rect().expect("non-empty after insert");
+            new_root.entries.push(Entry::Branch {
+                rect: old_rect,
+                child: Box::new(old_root),
+            });
+            let split_rect = split.bounding_rect().expect("split half is non-empty");
+            new_root.entries.push(Entry::Branch {
+                rect: split_rect,
+                child: Box::new(split),
+            });
+            self.root = new_root;
+        }
+
+        self.len += 1;
+    }
+
+    /// Insert `entry` into the subtree rooted at `node`, splitting and
+    /// returning the overflow half if `node` grew past `MAX_ENTRIES`.
+    fn insert_entry(node: &mut RNode<DIM>, entry: Entry<DIM>) -> Option<RNode<DIM>> {
+        if node.is_leaf {
+            node.entries.push(entry);
+        } else {
+            let best = node
+                .entries
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| {
+                    a.rect()
+                        .enlargement(entry.rect())
+                        .partial_cmp(&b.rect().enlargement(entry.rect()))
+                        .unwrap_or(Ordering::Equal)
+                })
+                .map(|(i, _)| i)
+                .expect("branch node always has entries");
+
+            let split_off = if let Entry::Branch { rect, child } = &mut node.entries[best] {
+                let split_off = Self::insert_entry(child, entry);
+                *rect = child.bounding_rect().expect("child is non-empty");
+                split_off
+            } else {
+                None
+            };
+
+            if let Some(split) = split_off {
+                let split_rect = split.bounding_rect().expect("split half is non-empty");
+                node.entries.push(Entry::Branch {
+                    rect: split_rect,
+                    child: Box::new(split),
+                });
+            }
+        }
+
+        if node.entries.len() > MAX_ENTRIES {
+            Some(Self::split(node))
+        } else {
+            None
+        }
+    }
+
+    /// Quadratic-cost split (Guttman): seed two groups from the pair of
+    /// entries whose combined bounding box wastes the most area, then greedily
+    /// assign the rest to whichever group needs the least enlargement.
+    fn split(node: &mut RNode<DIM>) -> RNode<DIM> {
+        let entries = std::mem::take(&mut node.entries);
+        let n = entries.len();
+
+        let mut seed_a = 0;
+        let mut seed_b = 1;
+        let mut worst_waste = f64::NEG_INFINITY;
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let combined = entries[i].rect().union(entries[j].rect());
+                let waste = combined.area() - entries[i].rect().area() - entries[j].rect().area();
+                if waste > worst_waste {
+                    worst_waste = waste;
+                    seed_a = i;
+                    seed_b = j;
+                }
+            }
+        }
+
+        let mut remaining: Vec<Entry<DIM>> = Vec::with_capacity(n - 2);
+        let mut group_a = Vec::new();
+        let mut group_b = Vec::new();
+        for (idx, entry) in entries.into_iter().enumerate() {
+            if idx == seed_a {
+                group_a.push(entry);
+            } else if idx == seed_b {
+                group_b.push(entry);
+            } else {
+                remaining.push(entry);
+            }
+        }
+
+        let mut rect_a = *group_a[0].rect();
+        let mut rect_b = *group_b[0].rect();
+        let mut unplaced = remaining.len();
+
+        for entry in remaining {
+            unplaced -= 1;
+
+            // Force remaining entries into whichever group would otherwise
+            // underflow below MIN_ENTRIES once every entry is placed.
+            if group_a.len() + unplaced < MIN_ENTRIES {
+                rect_a = rect_a.union(entry.rect());
+                group_a.push(entry);
+                continue;
+            }
+            if group_b.len() + unplaced < MIN_ENTRIES {
+                rect_b = rect_b.union(entry.rect());
+                group_b.push(entry);
+                continue;
+            }
+
+            let enlarge_a = rect_a.enlargement(entry.rect());
+            let enlarge_b = rect_b.enlargement(entry.rect());
+
+            if enlarge_a < enlarge_b
+                || (enlarge_a == enlarge_b && group_a.len() <= group_b.len())
+            {
+                rect_a = rect_a.union(entry.rect());
+                group_a.push(entry);
+            } else {
+                rect_b = rect_b.union(entry.rect());
+                group_b.push(entry);
+            }
+        }
+
+        node.entries = group_a;
+        RNode {
+            is_leaf: node.is_leaf,
+            entries: group_b,
+        }
+    }
+
+    /// Remove a node's coordinate from the index, if present.
+    pub fn remove(&mut self, node_id: &str) -> bool {
+        let removed = Self::remove_from(&mut self.root, node_id);
+        if removed {
+            self.len -= 1;
+            self.points.remove(node_id);
+        }
+        removed
+    }
+
+    /// The coordinate a node was indexed under, if any.
+    pub fn point_of(&self, node_id: &str) -> Option<[f64; DIM]> {
+        self.points.get(node_id).copied()
+    }
+
+    /// An admissible A* heuristic based on straight-line (Euclidean) distance
+    /// to `target`'s indexed coordinate. Nodes missing from the index (or if
+    /// `target` itself isn't indexed) contribute a heuristic of `0.0`, which
+    /// keeps the estimate admissible by falling back to uninformed search.
+    pub fn straight_line_heuristic<'a>(&'a self, target: &str) -> impl Fn(&str) -> f64 + 'a {
+        let target_point = self.point_of(target);
+        move |node_id: &str| match (target_point, self.point_of(node_id)) {
+            (Some(target_point), Some(point)) => {
+                (0..DIM)
+                    .map(|i| (point[i] - target_point[i]).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn remove_from(node: &mut RNode<DIM>, node_id: &str) -> bool {
+        if node.is_leaf {
+            let before = node.entries.len();
+            node.entries.retain(|e| match e {
+                Entry::Leaf { node_id: id, .. } => id != node_id,
+                Entry::Branch { .. } => true,
+            });
+            return node.entries.len() != before;
+        }
+
+        for entry in node.entries.iter_mut() {
+            if let Entry::Branch { rect, child } = entry {
+                if Self::remove_from(child, node_id) {
+                    if let Some(new_rect) = child.bounding_rect() {
+                        *rect = new_rect;
+                    }
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// The `n` nodes nearest to `point`, ordered closest-first.
+    ///
+    /// Uses best-first search over the R-tree: a min-heap (by squared
+    /// distance to `point`) of not-yet-expanded subtrees and points, so
+    /// branches whose bounding box is already farther than the `n`th-best
+    /// point found so far are never descended into.
+    pub fn nearest_neighbors(&self, point: [f64; DIM], n: usize) -> Vec<String> {
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let mut heap: BinaryHeap<NnCandidate<DIM>> = BinaryHeap::new();
+        heap.push(NnCandidate {
+            dist_sq: self.root.bounding_rect().map_or(0.0, |r| r.min_dist_sq(&point)),
+            item: NnItem::Subtree(&self.root),
+        });
+
+        let mut results = Vec::with_capacity(n);
+        while let Some(NnCandidate { item, .. }) = heap.pop() {
+            match item {
+                NnItem::Subtree(node) => {
+                    for entry in &node.entries {
+                        match entry {
+                            Entry::Leaf { rect, node_id } => {
+                                heap.push(NnCandidate {
+                                    dist_sq: rect.min_dist_sq(&point),
+                                    item: NnItem::Point(node_id),
+                                });
+                            }
+                            Entry::Branch { rect, child } => {
+                                heap.push(NnCandidate {
+                                    dist_sq: rect.min_dist_sq(&point),
+                                    item: NnItem::Subtree(child),
+                                });
+                            }
+                        }
+                    }
+                }
+                NnItem::Point(node_id) => {
+                    results.push(node_id.to_string());
+                    if results.len() == n {
+                        break;
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
+    /// All node IDs within `radius` of `point`, ordered closest-first.
+    pub fn within_radius(&self, point: [f64; DIM], radius: f64) -> Vec<String> {
+        let radius_sq = radius * radius;
+        let mut matches = Vec::new();
+        Self::collect_within_radius(&self.root, &point, radius_sq, &mut matches);
+        matches.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        matches.into_iter().map(|(id, _)| id).collect()
+    }
+
+    fn collect_within_radius(
+        node: &RNode<DIM>,
+        point: &[f64; DIM],
+        radius_sq: f64,
+        out: &mut Vec<(String, f64)>,
+    ) {
+        for entry in &node.entries {
+            if entry.rect().min_dist_sq(point) > radius_sq {
+                continue;
+            }
+            match entry {
+                Entry::Leaf { rect, node_id } => {
+                    let dist_sq = rect.min_dist_sq(point);
+                    if dist_sq <= radius_sq {
+                        out.push((node_id.clone(), dist_sq));
+                    }
+                }
+                Entry::Branch { child, .. } => {
+                    Self::collect_within_radius(child, point, radius_sq, out);
+                }
+            }
+        }
+    }
+
+    /// All node IDs whose coordinate falls within the axis-aligned box
+    /// `[min, max]` (inclusive).
+    pub fn within_bbox(&self, min: [f64; DIM], max: [f64; DIM]) -> Vec<String> {
+        let query = Rect { min, max };
+        let mut matches = Vec::new();
+        Self::collect_within_bbox(&self.root, &query, &mut matches);
+        matches
+    }
+
+    fn collect_within_bbox(node: &RNode<DIM>, query: &Rect<DIM>, out: &mut Vec<String>) {
+        for entry in &node.entries {
+            if !entry.rect().intersects(query) {
+                continue;
+            }
+            match entry {
+                Entry::Leaf { rect, node_id } => {
+                    if query.contains_point(&rect.min) {
+                        out.push(node_id.clone());
+                    }
+                }
+                Entry::Branch { child, .. } => {
+                    Self::collect_within_bbox(child, query, out);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_nearest_neighbors() {
+        let mut index: SpatialIndex<2> = SpatialIndex::new();
+        index.insert("a", [0.0, 0.0]);
+        index.insert("b", [1.0, 0.0]);
+        index.insert("c", [5.0, 5.0]);
+
+        let nearest = index.nearest_neighbors([0.1, 0.0], 2);
+        assert_eq!(nearest, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_within_radius() {
+        let mut index: SpatialIndex<2> = SpatialIndex::new();
+        index.insert("a", [0.0, 0.0]);
+        index.insert("b", [1.0, 0.0]);
+        index.insert("c", [10.0, 10.0]);
+
+        let nearby = index.within_radius([0.0, 0.0], 2.0);
+        assert_eq!(nearby, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_within_bbox() {
+        let mut index: SpatialIndex<2> = SpatialIndex::new();
+        index.insert("a", [0.0, 0.0]);
+        index.insert("b", [5.0, 5.0]);
+        index.insert("c", [10.0, 10.0]);
+
+        let mut inside = index.within_bbox([0.0, 0.0], [5.0, 5.0]);
+        inside.sort();
+        assert_eq!(inside, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut index: SpatialIndex<2> = SpatialIndex::new();
+        index.insert("a", [0.0, 0.0]);
+        index.insert("b", [1.0, 1.0]);
+
+        assert!(index.remove("a"));
+        assert_eq!(index.len(), 1);
+        assert!(!index.within_bbox([0.0, 0.0], [0.0, 0.0]).contains(&"a".to_string()));
+    }
+
+    #[test]
+    fn test_straight_line_heuristic_is_euclidean_distance() {
+        let mut index: SpatialIndex<2> = SpatialIndex::new();
+        index.insert("start", [0.0, 0.0]);
+        index.insert("end", [3.0, 4.0]);
+
+        let h = index.straight_line_heuristic("end");
+        assert_eq!(h("start"), 5.0);
+        assert_eq!(h("end"), 0.0);
+        // Unindexed nodes fall back to a zero (still admissible) estimate.
+        assert_eq!(h("missing"), 0.0);
+    }
+
+    #[test]
+    fn test_insert_many_triggers_splits() {
+        let mut index: SpatialIndex<2> = SpatialIndex::new();
+        for i in 0..200 {
+            index.insert(format!("n{i}"), [i as f64, i as f64]);
+        }
+
+        assert_eq!(index.len(), 200);
+        let nearest = index.nearest_neighbors([100.0, 100.0], 1);
+        assert_eq!(nearest, vec!["n100".to_string()]);
+    }
+}
@@ -0,0 +1,129 @@
+//! Generation-stamped visited set for fast repeated traversals
+//!
+//! `DepthFirstSearch`/`BreadthFirstSearch` used to allocate and grow a
+//! `HashSet<String>` per call and hash a full string ID on every membership
+//! test, which dominates cost on large graphs run over many short
+//! traversals. `VisitedSet` instead interns node IDs to dense `u32`
+//! handles (the same bidirectional-interner shape `neodb-storage::Index`
+//! uses for its posting lists) and tracks membership with a `Vec<u32>` of
+//! generation stamps alongside a current-generation counter: marking a node
+//! writes the current generation into its slot, and testing membership just
+//! compares the stored stamp to the current generation. Clearing becomes an
+//! O(1) generation bump instead of reallocating, so a `VisitedSet` can be
+//! held across many traversals on the same graph.
+
+use std::collections::HashMap;
+
+/// Dense handle for a node ID interned into a `VisitedSet`. Callers that
+/// already hold one can test/mark membership without hashing a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle(u32);
+
+/// Reusable visited-tracking set: intern string node IDs once, then mark and
+/// test membership by generation stamp instead of growing/hashing a set of
+/// strings per traversal.
+#[derive(Debug)]
+pub struct VisitedSet {
+    ids: HashMap<String, u32>,
+    strings: Vec<String>,
+    stamps: Vec<u32>,
+    generation: u32,
+}
+
+impl VisitedSet {
+    pub fn new() -> Self {
+        // Start at generation 1 so a freshly-interned node's zeroed stamp
+        // doesn't read as already marked.
+        Self { ids: HashMap::new(), strings: Vec::new(), stamps: Vec::new(), generation: 1 }
+    }
+
+    /// Look up `node_id`'s dense handle, interning it the first time it's
+    /// seen. The handle is stable for the lifetime of this `VisitedSet`.
+    pub fn intern(&mut self, node_id: &str) -> NodeHandle {
+        if let Some(&id) = self.ids.get(node_id) {
+            return NodeHandle(id);
+        }
+
+        let id = self.strings.len() as u32;
+        self.strings.push(node_id.to_string());
+        self.stamps.push(0);
+        self.ids.insert(node_id.to_string(), id);
+        NodeHandle(id)
+    }
+
+    /// Mark `handle` visited in the current generation.
+    pub fn mark(&mut self, handle: NodeHandle) {
+        self.stamps[handle.0 as usize] = self.generation;
+    }
+
+    /// Whether `handle` was marked in the current generation.
+    pub fn is_marked(&self, handle: NodeHandle) -> bool {
+        self.stamps[handle.0 as usize] == self.generation
+    }
+
+    /// Intern `node_id` and mark it in one step, for callers without an
+    /// already-interned handle.
+    pub fn mark_str(&mut self, node_id: &str) -> NodeHandle {
+        let handle = self.intern(node_id);
+        self.mark(handle);
+        handle
+    }
+
+    /// Intern `node_id` and test whether it's marked in one step, for
+    /// callers without an already-interned handle.
+    pub fn is_marked_str(&mut self, node_id: &str) -> bool {
+        let handle = self.intern(node_id);
+        self.is_marked(handle)
+    }
+
+    /// Clear every mark in O(1) by bumping the current generation instead of
+    /// reallocating or rehashing the interned node IDs.
+    pub fn clear(&mut self) {
+        self.generation += 1;
+    }
+}
+
+impl Default for VisitedSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_and_test_round_trip() {
+        let mut set = VisitedSet::new();
+        assert!(!set.is_marked_str("a"));
+        set.mark_str("a");
+        assert!(set.is_marked_str("a"));
+        assert!(!set.is_marked_str("b"));
+    }
+
+    #[test]
+    fn test_clear_resets_membership_without_forgetting_handles() {
+        let mut set = VisitedSet::new();
+        let a = set.mark_str("a");
+        assert!(set.is_marked(a));
+
+        set.clear();
+        assert!(!set.is_marked(a));
+
+        // The handle is stable across a clear, so a caller holding one from
+        // a prior traversal can still use it without re-interning.
+        assert_eq!(set.intern("a"), a);
+    }
+
+    #[test]
+    fn test_handles_are_stable_and_distinct_per_node() {
+        let mut set = VisitedSet::new();
+        let a1 = set.intern("a");
+        let b = set.intern("b");
+        let a2 = set.intern("a");
+
+        assert_eq!(a1, a2);
+        assert_ne!(a1, b);
+    }
+}
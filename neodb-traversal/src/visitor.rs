@@ -28,6 +28,17 @@ pub trait Visitor {
     fn leave_node(&mut self, _node_id: &str) {}
 }
 
+/// Extends `Visitor` with edge costs and a goal heuristic so
+/// `BestFirstSearch` can do goal-directed search instead of plain
+/// uninformed DFS/BFS.
+pub trait WeightedVisitor: Visitor {
+    /// Cost of the edge from `from` to `to`. Must be non-negative.
+    fn edge_cost(&self, from: &str, to: &str) -> f32;
+
+    /// Estimated remaining cost from `node` to the goal.
+    fn heuristic(&self, node: &str) -> f32;
+}
+
 /// Simple collecting visitor that records visited nodes
 #[derive(Debug, Default)]
 pub struct CollectingVisitor {
@@ -199,6 +210,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_graph_visitor_tracks_visited_nodes() {
+        let mut visitor = TestGraphVisitor::new();
+
+        assert_eq!(visitor.visit_node("A"), VisitResult::Continue);
+        assert_eq!(visitor.get_neighbors("A"), vec!["B".to_string(), "C".to_string()]);
+        assert_eq!(visitor.visited, vec!["A".to_string()]);
+    }
+
     #[test]
     fn test_collecting_visitor() {
         let mut visitor = CollectingVisitor::new();